@@ -0,0 +1,58 @@
+//! Resolves a signature database against a running process and dumps the offsets.
+//!
+//! `cargo run --example dump_offsets --features signatures,toml -- game.exe signatures.toml out`
+
+#[cfg(any(windows, target_os = "linux"))]
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    use faithe::{
+        process::OwnedProcess,
+        signatures::{hash_image, Dump, DumpFormat, ModuleDump, SignatureDb},
+        types::access_rights::{PROCESS_QUERY_INFORMATION, PROCESS_VM_READ},
+    };
+    use std::{collections::HashSet, path::PathBuf};
+
+    let mut args = std::env::args().skip(1);
+    let (Some(process), Some(db)) = (args.next(), args.next()) else {
+        eprintln!("usage: dump_offsets <process> <signatures.toml> [output dir]");
+        std::process::exit(1);
+    };
+    let out = PathBuf::from(args.next().unwrap_or_else(|| ".".into()));
+
+    let proc = OwnedProcess::open_by_name(
+        process,
+        false,
+        PROCESS_VM_READ | PROCESS_QUERY_INFORMATION,
+    )?;
+    let db = SignatureDb::from_toml(&std::fs::read_to_string(db)?)?;
+    let resolved = db.resolve(&proc)?;
+
+    let names = db
+        .signatures
+        .iter()
+        .map(|s| s.module.as_str())
+        .collect::<HashSet<_>>();
+    let mut dump = Dump::new();
+    for module in proc.modules()?.filter(|m| names.contains(m.name.as_str())) {
+        // Hash of the file on disk changes only with the module's version.
+        let hash = hash_image(&std::fs::read(&module.path)?);
+        let mut entry = ModuleDump::new(&module.name, module.base_address, module.size, hash);
+        entry.add_resolved(&db, &resolved);
+        dump.modules.push(entry);
+    }
+
+    std::fs::create_dir_all(&out)?;
+    for (format, file) in [
+        (DumpFormat::Rust, "offsets.rs"),
+        (DumpFormat::Cpp, "offsets.hpp"),
+        (DumpFormat::CSharp, "Offsets.cs"),
+        (DumpFormat::Json, "offsets.json"),
+    ] {
+        std::fs::write(out.join(file), dump.render(format))?;
+    }
+    Ok(())
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+fn main() {
+    eprintln!("dump_offsets only supports Windows and Linux");
+}
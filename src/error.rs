@@ -2,10 +2,10 @@
 /// Error type for all mistakes made in faithe.
 #[derive(Debug)]
 pub enum FaitheError {
-    #[cfg(not(feature = "no-std"))]
+    #[cfg(all(windows, not(feature = "no-std")))]
     /// Error code returned from `GetLastError()` WinAPI.
     ErrorCode(windows::Win32::Foundation::WIN32_ERROR),
-    #[cfg(not(feature = "no-std"))]
+    #[cfg(all(windows, not(feature = "no-std")))]
     /// Error from `windows` crate
     WindowsError(windows::core::Error),
     /// No process with selected name were found.
@@ -27,8 +27,30 @@ pub enum FaitheError {
     InvalidPattern,
     /// Length of mask is not equal to the length of the pattern.
     PatternMaskMismatch,
+    /// Failed to decode an instruction.
+    InvalidInstruction,
+    /// Instruction doesn't refer to any address, e.g. it has no branch target or memory displacement.
+    NoInstructionTarget,
+    /// Failed to create a unique signature within the length limit.
+    SignatureNotUnique,
+    /// Headers of the executable image are malformed.
+    InvalidImage,
+    #[cfg(not(feature = "no-std"))]
+    /// I/O error, e.g. failed to open a file.
+    IoError(std::io::Error),
+    /// Signature can't be resolved in its mode, e.g. pattern is missing.
+    InvalidSignature,
+    #[cfg(not(feature = "no-std"))]
+    /// Signature database failed to parse.
+    InvalidDatabase(String),
+    #[cfg(not(feature = "no-std"))]
+    /// Step of the offset's resolution with the index failed, e.g. a pointer couldn't be read.
+    StepFailed(usize, Box<FaitheError>),
     /// Tried to resolve function pointer twice.
     AlreadyResolved,
+    #[cfg(all(unix, not(feature = "no-std")))]
+    /// Message of `dlerror()` after `dlopen` failed.
+    DlError(String),
 }
 
 pub(crate) type Result<T> = core::result::Result<T, FaitheError>;
@@ -43,16 +65,31 @@ cfg_if::cfg_if! {
             }
         }
 
+        #[cfg(windows)]
         impl From<windows::core::Error> for FaitheError {
             fn from(e: windows::core::Error) -> Self {
                 Self::WindowsError(e)
             }
         }
 
+        impl From<std::io::Error> for FaitheError {
+            fn from(e: std::io::Error) -> Self {
+                Self::IoError(e)
+            }
+        }
+
+        #[cfg(windows)]
         impl FaitheError {
             pub(crate) fn last_error() -> Self {
                 unsafe { Self::ErrorCode(windows::Win32::Foundation::GetLastError()) }
             }
         }
+
+        #[cfg(unix)]
+        impl FaitheError {
+            pub(crate) fn last_error() -> Self {
+                Self::IoError(std::io::Error::last_os_error())
+            }
+        }
     }
 }
@@ -0,0 +1,205 @@
+use crate::{
+    memory::MemoryRegion,
+    procfs,
+    types::{
+        allocation_types::{MEM_COMMIT, MEM_RESERVE, VIRTUAL_ALLOCATION_TYPE},
+        free_types::{MEM_RELEASE, VIRTUAL_FREE_TYPE},
+        MemoryProtection,
+    },
+    FaitheError,
+};
+use std::{io, ops::Range};
+
+/// Expands the range to the pages it touches.
+/// Unlike Windows API `mprotect`, `madvise` and `munmap` require aligned addresses.
+fn page_range(address: usize, size: usize) -> Range<usize> {
+    let page = unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize };
+    (address & !(page - 1))..((address + size + page - 1) & !(page - 1))
+}
+
+/// Changes the protection of memory pages of the current process.
+/// Returns the previous protection of the page at `address`, `mprotect` doesn't report it so it's queried first.
+pub fn protect(
+    address: *mut (),
+    size: usize,
+    new_protection: MemoryProtection,
+) -> crate::Result<MemoryProtection> {
+    let old = query(address as usize)?.protection;
+    let pages = page_range(address as usize, size);
+    if unsafe { libc::mprotect(pages.start as _, pages.len(), new_protection.to_os()) } == -1 {
+        Err(FaitheError::last_error())
+    } else {
+        Ok(old)
+    }
+}
+
+/// Tries to allocate memory pages in the current process.
+/// Pages are mapped if `MEM_RESERVE` is passed and are inaccessible unless `MEM_COMMIT` is passed too,
+/// `MEM_COMMIT` alone changes the protection of already reserved pages at `address`.
+/// Non-zero `address` is mapped with `MAP_FIXED_NOREPLACE`, so the allocation fails if it is taken.
+/// On success returns the address of allocated region.
+/// ```
+/// # use faithe::{internal::{allocate, free, protect}, types::MemoryProtection};
+/// # use faithe::types::{allocation_types::{MEM_COMMIT, MEM_RESERVE}, free_types::MEM_RELEASE};
+/// let chunk = allocate(0, 0x1000, MEM_RESERVE, MemoryProtection::NONE)?;
+/// allocate(chunk as usize, 0x1000, MEM_COMMIT, MemoryProtection::READ_WRITE)?;
+/// unsafe { *(chunk as *mut u32) = 1337 };
+///
+/// let old = protect(chunk, 0x1000, MemoryProtection::READ)?;
+/// assert_eq!(old, MemoryProtection::READ_WRITE);
+/// assert_eq!(faithe::internal::query(chunk as usize)?.protection, MemoryProtection::READ);
+///
+/// free(chunk as usize, 0x1000, MEM_RELEASE)?;
+/// # Ok::<(), faithe::FaitheError>(())
+/// ```
+pub fn allocate(
+    address: usize,
+    size: usize,
+    allocation_type: VIRTUAL_ALLOCATION_TYPE,
+    protection: MemoryProtection,
+) -> crate::Result<*mut ()> {
+    if !allocation_type.contains(MEM_RESERVE) {
+        protect(address as _, size, protection)?;
+        return Ok(address as _);
+    }
+
+    let protection = if allocation_type.contains(MEM_COMMIT) {
+        protection.to_os()
+    } else {
+        libc::PROT_NONE
+    };
+    let mut flags = libc::MAP_PRIVATE | libc::MAP_ANONYMOUS;
+    if address != 0 {
+        flags |= libc::MAP_FIXED_NOREPLACE;
+    }
+
+    let region = unsafe { libc::mmap(address as _, size, protection, flags, -1, 0) };
+    if region == libc::MAP_FAILED {
+        Err(FaitheError::last_error())
+    } else {
+        Ok(region as _)
+    }
+}
+
+/// Tries to free memory pages in the current process.
+/// Unlike `VirtualFree`, `MEM_RELEASE` requires the size of the allocation, exactly the passed range is unmapped.
+/// Decommitted pages stay reserved and are zeroed when committed again.
+pub fn free(address: usize, size: usize, free_type: VIRTUAL_FREE_TYPE) -> crate::Result<()> {
+    let pages = page_range(address, size);
+    let result = unsafe {
+        if free_type.contains(MEM_RELEASE) {
+            libc::munmap(pages.start as _, pages.len())
+        } else {
+            match libc::madvise(pages.start as _, pages.len(), libc::MADV_DONTNEED) {
+                0 => libc::mprotect(pages.start as _, pages.len(), libc::PROT_NONE),
+                err => err,
+            }
+        }
+    };
+
+    if result == -1 {
+        Err(FaitheError::last_error())
+    } else {
+        Ok(())
+    }
+}
+
+/// Queries the memory region that contains `address` from `/proc/self/maps`.
+pub fn query(address: usize) -> crate::Result<MemoryRegion> {
+    procfs::query(std::process::id(), address)
+}
+
+/// Copies memory at `address` into `buf` as far as it is readable, instead of crashing on inaccessible pages.
+/// Returns the amount of bytes read.
+pub fn read_buf(address: usize, mut buf: impl AsMut<[u8]>) -> crate::Result<usize> {
+    let buf = buf.as_mut();
+    let local = libc::iovec {
+        iov_base: buf.as_mut_ptr() as _,
+        iov_len: buf.len(),
+    };
+    let remote = libc::iovec {
+        iov_base: address as _,
+        iov_len: buf.len(),
+    };
+
+    let read = unsafe { libc::process_vm_readv(libc::getpid(), &local, 1, &remote, 1, 0) };
+    copied(
+        read,
+        address,
+        buf.len(),
+        MemoryProtection::READ,
+        |offset, len| unsafe {
+            core::ptr::copy_nonoverlapping(
+                (address + offset) as *const u8,
+                buf[offset..].as_mut_ptr(),
+                len,
+            )
+        },
+    )
+}
+
+/// Copies `buf` into memory at `address` as far as it is writable, instead of crashing on inaccessible pages.
+/// Returns the amount of bytes written.
+pub fn write_buf(address: usize, buf: impl AsRef<[u8]>) -> crate::Result<usize> {
+    let buf = buf.as_ref();
+    let local = libc::iovec {
+        iov_base: buf.as_ptr() as _,
+        iov_len: buf.len(),
+    };
+    let remote = libc::iovec {
+        iov_base: address as _,
+        iov_len: buf.len(),
+    };
+
+    let written = unsafe { libc::process_vm_writev(libc::getpid(), &local, 1, &remote, 1, 0) };
+    copied(
+        written,
+        address,
+        buf.len(),
+        MemoryProtection::WRITE,
+        |offset, len| unsafe {
+            core::ptr::copy_nonoverlapping(
+                buf[offset..].as_ptr(),
+                (address + offset) as *mut u8,
+                len,
+            )
+        },
+    )
+}
+
+/// Handles the result of `process_vm_readv` or `process_vm_writev` on the current process.
+/// Inaccessible memory at `address` is reported as nothing copied,
+/// if the syscall is not permitted, e.g. by seccomp, regions with `access` are copied with `copy` instead.
+fn copied(
+    result: isize,
+    address: usize,
+    len: usize,
+    access: MemoryProtection,
+    mut copy: impl FnMut(usize, usize),
+) -> crate::Result<usize> {
+    if result >= 0 {
+        return Ok(result as usize);
+    }
+
+    let error = io::Error::last_os_error();
+    match error.raw_os_error() {
+        Some(libc::EFAULT) => return Ok(0),
+        Some(libc::ENOSYS | libc::EPERM) => {}
+        _ => return Err(error.into()),
+    }
+
+    let regions = procfs::regions(std::process::id())?;
+    let mut done = 0;
+    while done < len {
+        let at = address + done;
+        match regions.iter().find(|r| r.start <= at && at < r.end) {
+            Some(region) if region.protection.contains(access) => {
+                let chunk = (region.end - at).min(len - done);
+                copy(done, chunk);
+                done += chunk;
+            }
+            _ => break,
+        }
+    }
+    Ok(done)
+}
@@ -0,0 +1,8 @@
+mod memory;
+pub use memory::*;
+
+mod module;
+pub use module::*;
+
+mod thread;
+pub use thread::*;
@@ -0,0 +1,154 @@
+use super::super::ModuleInfo;
+use crate::FaitheError;
+use std::{
+    ffi::{CStr, CString},
+    os::raw::{c_int, c_void},
+    path::Path,
+    ptr::NonNull,
+};
+
+/// Shared object loaded into the current process.
+struct LoadedObject {
+    /// Name passed to `dlopen`, empty for the main program.
+    name: CString,
+    /// Full path to the file.
+    path: String,
+    info: ModuleInfo,
+}
+
+impl LoadedObject {
+    fn file_name(&self) -> Option<&str> {
+        Path::new(&self.path).file_name()?.to_str()
+    }
+
+    /// Module matches if its file name or its full path is equal to `mod_name`.
+    fn is(&self, mod_name: &str) -> bool {
+        self.file_name() == Some(mod_name) || self.path == mod_name
+    }
+}
+
+/// Lists loaded objects with `dl_iterate_phdr`.
+/// Image spans from the lowest to the highest `PT_LOAD` segment.
+fn loaded_objects() -> Vec<LoadedObject> {
+    unsafe extern "C" fn callback(
+        info: *mut libc::dl_phdr_info,
+        _size: usize,
+        data: *mut c_void,
+    ) -> c_int {
+        let info = &*info;
+        let objects = &mut *(data as *mut Vec<LoadedObject>);
+
+        let phdrs = std::slice::from_raw_parts(info.dlpi_phdr, info.dlpi_phnum as usize);
+        let loads = phdrs.iter().filter(|p| p.p_type == libc::PT_LOAD);
+        let start = loads.clone().map(|p| p.p_vaddr as usize).min();
+        let end = loads.map(|p| (p.p_vaddr + p.p_memsz) as usize).max();
+        let (Some(start), Some(end)) = (start, end) else {
+            return 0;
+        };
+        let page = libc::sysconf(libc::_SC_PAGESIZE) as usize;
+        let base = info.dlpi_addr as usize + (start & !(page - 1));
+
+        // The main program has no name.
+        let name = CStr::from_ptr(info.dlpi_name);
+        let path = match name.to_str() {
+            Ok("") => std::env::current_exe()
+                .map(|p| p.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            _ => name.to_string_lossy().into_owned(),
+        };
+
+        // First segment starts with ELF header, `e_entry` is at the same offset in both classes.
+        let header = base as *const u8;
+        let entry = if std::slice::from_raw_parts(header, 4) == b"\x7FELF" {
+            header.add(0x18).cast::<usize>().read_unaligned()
+        } else {
+            0
+        };
+
+        objects.push(LoadedObject {
+            name: name.to_owned(),
+            path,
+            info: ModuleInfo {
+                dll_base: base as _,
+                image_size: info.dlpi_addr as usize + end - base,
+                entry_point: if entry == 0 {
+                    std::ptr::null_mut()
+                } else {
+                    (info.dlpi_addr as usize + entry) as _
+                },
+            },
+        });
+        0
+    }
+
+    let mut objects: Vec<LoadedObject> = vec![];
+    unsafe {
+        libc::dl_iterate_phdr(Some(callback), &mut objects as *mut _ as _);
+    }
+    objects
+}
+
+fn find_object(mod_name: &str) -> crate::Result<LoadedObject> {
+    loaded_objects()
+        .into_iter()
+        .find(|o| o.is(mod_name))
+        .ok_or(FaitheError::ModuleNotFound)
+}
+
+/// Either loads library with `dlopen` or returns an address of already existing module.
+/// Returns the base address of the module, not the handle returned by `dlopen`.
+pub fn load_library(lib_name: impl AsRef<str>) -> crate::Result<NonNull<()>> {
+    let name = CString::new(lib_name.as_ref()).map_err(|_| FaitheError::InvalidString)?;
+
+    unsafe {
+        let handle = libc::dlopen(name.as_ptr(), libc::RTLD_NOW);
+        if handle.is_null() {
+            let error = CStr::from_ptr(libc::dlerror());
+            return Err(FaitheError::DlError(error.to_string_lossy().into_owned()));
+        }
+
+        // Handles are opaque, the module is the one that is opened with the same handle again.
+        let same_handle = |object: &LoadedObject| {
+            let name = match object.name.as_bytes() {
+                [] => std::ptr::null(),
+                _ => object.name.as_ptr(),
+            };
+            let other = libc::dlopen(name, libc::RTLD_NOW | libc::RTLD_NOLOAD);
+            if !other.is_null() {
+                libc::dlclose(other);
+            }
+            other == handle
+        };
+
+        loaded_objects()
+            .into_iter()
+            .find(same_handle)
+            .and_then(|o| NonNull::new(o.info.dll_base))
+            .ok_or(FaitheError::ModuleNotFound)
+    }
+}
+
+/// Returns the base address of the module or Err if failed to find the specified module.
+/// Module is searched by its file name, e.g. `libc.so.6`, or by its full path.
+/// ```
+/// # use faithe::internal::{get_module_address, get_module_information, load_library};
+/// let exe = std::env::current_exe()?;
+/// let base = get_module_address(exe.file_name().unwrap().to_str().unwrap())?;
+/// // Executable starts with ELF header.
+/// assert_eq!(unsafe { *(base as *const [u8; 4]) }, *b"\x7FELF");
+///
+/// let libc = get_module_information("libc.so.6")?;
+/// assert!(libc.image_size > 0 && !libc.entry_point.is_null());
+/// assert_eq!(load_library("libc.so.6")?.as_ptr(), libc.dll_base);
+/// assert!(load_library("libdoesnotexist.so").is_err());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn get_module_address(mod_name: impl AsRef<str>) -> crate::Result<*mut ()> {
+    find_object(mod_name.as_ref()).map(|o| o.info.dll_base)
+}
+
+/// Returns information about the specified module.
+/// Module is searched the same way [`get_module_address`] does.
+pub fn get_module_information(mod_name: impl AsRef<str>) -> crate::Result<ModuleInfo> {
+    find_object(mod_name.as_ref()).map(|o| o.info)
+}
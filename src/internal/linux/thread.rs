@@ -0,0 +1,44 @@
+type ThreadInit<T> = unsafe extern "system" fn(Option<Box<T>>) -> u32;
+
+/// Creates new thread with default parameters.
+/// Returns the id of the thread, the same one `gettid` returns in it.
+/// # Panics
+/// If failed to create a new thread.
+/// ```
+/// # use faithe::internal::create_thread;
+/// use std::sync::mpsc::{channel, Sender};
+///
+/// unsafe extern "system" fn init(tx: Option<Box<Sender<u32>>>) -> u32 {
+///     tx.unwrap().send(libc::gettid() as u32).unwrap();
+///     0
+/// }
+///
+/// let (tx, rx) = channel();
+/// let id = create_thread(init, Some(tx));
+/// assert_eq!(rx.recv().unwrap(), id);
+/// ```
+pub fn create_thread<T>(init: ThreadInit<T>, param: Option<T>) -> u32 {
+    /// Parameter is handed over to the thread without `Send` bound, the same way `CreateThread` does.
+    struct Start(unsafe extern "system" fn(*mut ()) -> u32, *mut ());
+    unsafe impl Send for Start {}
+
+    impl Start {
+        unsafe fn run(self) {
+            (self.0)(self.1);
+        }
+    }
+
+    let start = unsafe {
+        Start(
+            std::mem::transmute::<ThreadInit<T>, unsafe extern "system" fn(*mut ()) -> u32>(init),
+            param.map_or(std::ptr::null_mut(), |p| Box::into_raw(Box::new(p)) as _),
+        )
+    };
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || unsafe {
+        tx.send(libc::syscall(libc::SYS_gettid) as u32).ok();
+        start.run();
+    });
+    rx.recv().unwrap()
+}
@@ -76,7 +76,6 @@ pub fn free(
 }
 
 /// Queries basic information about memory region at `address`.
-#[cfg(windows)]
 pub fn query(address: usize) -> crate::Result<crate::types::MemoryBasicInformation> {
     unsafe {
         let mut mem_info = zeroed();
@@ -87,3 +86,66 @@ pub fn query(address: usize) -> crate::Result<crate::types::MemoryBasicInformati
         }
     }
 }
+
+/// Copies memory at `address` into `buf` as far as it is readable, instead of crashing on inaccessible pages.
+/// Returns the amount of bytes read.
+pub fn read_buf(address: usize, mut buf: impl AsMut<[u8]>) -> crate::Result<usize> {
+    use windows::Win32::System::Memory::{MEM_COMMIT, PAGE_GUARD, PAGE_NOACCESS};
+
+    let buf = buf.as_mut();
+    let mut read = 0;
+    while read < buf.len() {
+        let region = query(address + read)?;
+        if region.state != MEM_COMMIT || region.protection.0 & (PAGE_GUARD.0 | PAGE_NOACCESS.0) != 0
+        {
+            break;
+        }
+
+        let len =
+            (region.base_address + region.region_size - (address + read)).min(buf.len() - read);
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                (address + read) as *const u8,
+                buf[read..].as_mut_ptr(),
+                len,
+            );
+        }
+        read += len;
+    }
+    Ok(read)
+}
+
+/// Copies `buf` into memory at `address` as far as it is writable, instead of crashing on inaccessible pages.
+/// Returns the amount of bytes written.
+pub fn write_buf(address: usize, buf: impl AsRef<[u8]>) -> crate::Result<usize> {
+    use windows::Win32::System::Memory::{
+        MEM_COMMIT, PAGE_EXECUTE_READWRITE, PAGE_EXECUTE_WRITECOPY, PAGE_GUARD, PAGE_READWRITE,
+        PAGE_WRITECOPY,
+    };
+
+    let writable =
+        PAGE_READWRITE.0 | PAGE_WRITECOPY.0 | PAGE_EXECUTE_READWRITE.0 | PAGE_EXECUTE_WRITECOPY.0;
+    let buf = buf.as_ref();
+    let mut written = 0;
+    while written < buf.len() {
+        let region = query(address + written)?;
+        if region.state != MEM_COMMIT
+            || region.protection.0 & PAGE_GUARD.0 != 0
+            || region.protection.0 & writable == 0
+        {
+            break;
+        }
+
+        let len = (region.base_address + region.region_size - (address + written))
+            .min(buf.len() - written);
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                buf[written..].as_ptr(),
+                (address + written) as *mut u8,
+                len,
+            );
+        }
+        written += len;
+    }
+    Ok(written)
+}
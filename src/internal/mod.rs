@@ -1,16 +1,29 @@
 use crate::{terminated_array, FaitheError};
 
-mod memory;
-pub use memory::*;
+mod module_info;
+pub use module_info::*;
 
-mod thread;
-pub use thread::*;
+mod pat;
+pub use pat::*;
 
-mod process;
-pub use process::*;
+cfg_if::cfg_if! {
+    if #[cfg(windows)] {
+        mod memory;
+        pub use memory::*;
 
-mod module;
-pub use module::*;
+        mod thread;
+        pub use thread::*;
+
+        mod process;
+        pub use process::*;
+
+        mod module;
+        pub use module::*;
+    } else {
+        mod linux;
+        pub use linux::*;
+    }
+}
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
@@ -18,21 +31,39 @@ extern crate alloc;
 /// Resolves multilevel pointer.
 /// # Behavior
 /// It begins from adding to base first offset and reading a value on this address, assigns to
-/// base readed value and so on.
+/// base readed value and so on. Returns null pointer if some pointer on the path can't be read.
 /// # Safety
-/// You need to make sure beforehand that all offsets will lead to valid memory addresses.
+/// Memory on the path is checked before it's read, but the returned pointer may still be dangling.
+/// ```
+/// # use faithe::internal::follow_pointer_path;
+/// let value = 1337u32;
+/// let first = &value as *const u32 as usize;
+/// let second = &first as *const usize as usize;
+/// unsafe {
+///     assert_eq!(*follow_pointer_path::<2, u32>(&second as *const usize as _, [0, 0]), 1337);
+///     assert!(follow_pointer_path::<1, u32>(core::ptr::null(), [0]).is_null());
+/// }
+/// ```
 #[inline]
 pub unsafe fn follow_pointer_path<const I: usize, T>(
-    mut base: *const u8,
+    base: *const u8,
     offsets: [usize; I],
 ) -> *const T {
-    for offset in &offsets {
-        base = *((base as usize + *offset) as *const usize) as _;
+    use crate::memory::{CurrentProcess, MemoryReader};
+
+    let mut base = base as usize;
+    for offset in offsets {
+        match CurrentProcess.read_pointer(base.wrapping_add(offset)) {
+            Ok(next) => base = next,
+            Err(_) => return core::ptr::null(),
+        }
     }
     base as _
 }
 
 /// Reads zero terminated string at `ptr`.
+/// # Safety
+/// `ptr` must be valid and point to a zero terminated string.
 #[inline]
 pub unsafe fn read_string<'a>(ptr: *const i8) -> crate::Result<&'a str> {
     core::str::from_utf8(terminated_array(ptr as *const u8, 0))
@@ -40,23 +71,29 @@ pub unsafe fn read_string<'a>(ptr: *const i8) -> crate::Result<&'a str> {
 }
 
 /// Reads zero terminated string at `ptr`.
+/// # Safety
+/// `ptr` must be valid and point to a zero terminated string.
 #[inline]
 pub unsafe fn read_string_unchecked<'a>(ptr: *const i8) -> &'a str {
     core::str::from_utf8_unchecked(terminated_array(ptr as *const u8, 0))
 }
 
 /// Reads zero terminated string at `ptr`.
+/// # Safety
+/// `ptr` must be valid and point to a zero terminated string.
 #[cfg(feature = "alloc")]
 #[inline]
-pub unsafe fn read_wide_string<'a>(ptr: *const u16) -> crate::Result<alloc::string::String> {
+pub unsafe fn read_wide_string(ptr: *const u16) -> crate::Result<alloc::string::String> {
     alloc::string::String::from_utf16(terminated_array(ptr, 0))
         .map_err(|_| FaitheError::InvalidString)
 }
 
 /// Reads zero terminated string at `ptr`.
+/// # Safety
+/// `ptr` must be valid and point to a zero terminated string.
 #[cfg(feature = "alloc")]
 #[inline]
-pub unsafe fn read_wide_string_unchecked<'a>(ptr: *const u16) -> alloc::string::String {
+pub unsafe fn read_wide_string_unchecked(ptr: *const u16) -> alloc::string::String {
     alloc::string::String::from_utf16_lossy(terminated_array(ptr, 0))
 }
 
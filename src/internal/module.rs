@@ -1,22 +1,11 @@
+use super::ModuleInfo;
+use crate::FaitheError;
 use std::ptr::NonNull;
-
-use crate::{pattern::Pattern, FaitheError};
 use windows::{
     core::PCWSTR,
     Win32::System::{LibraryLoader::LoadLibraryW, ProcessStatus::MODULEINFO},
 };
 
-/// Basic information about process's module.
-#[derive(Debug, Clone)]
-pub struct ModuleInfo {
-    /// Base of the module.
-    pub dll_base: *mut (),
-    /// Size of the image.
-    pub image_size: usize,
-    /// Address of the dll's entry point.
-    pub entry_point: *mut (),
-}
-
 impl From<MODULEINFO> for ModuleInfo {
     fn from(mi: MODULEINFO) -> Self {
         Self {
@@ -151,15 +140,3 @@ pub fn get_module_information(mod_name: impl AsRef<str>) -> crate::Result<Module
         }
     }
 }
-
-/// Searches module for specific memory pattern.
-pub fn find_pattern(mod_name: impl AsRef<str>, pat: Pattern) -> crate::Result<Option<NonNull<()>>> {
-    let info = get_module_information(mod_name)?;
-
-    for addr in (info.dll_base as usize)..(info.dll_base as usize + info.image_size) {
-        if unsafe { pat.matches(std::slice::from_raw_parts(addr as _, pat.len())) } {
-            return Ok(NonNull::new(addr as _));
-        }
-    }
-    Ok(None)
-}
@@ -0,0 +1,97 @@
+/// Basic information about process's module.
+#[derive(Debug, Clone)]
+pub struct ModuleInfo {
+    /// Base of the module.
+    pub dll_base: *mut (),
+    /// Size of the image.
+    pub image_size: usize,
+    /// Address of the dll's entry point.
+    pub entry_point: *mut (),
+}
+
+impl ModuleInfo {
+    /// Returns readable parts of the module's image with their addresses.
+    /// Gaps between segments of ELF images are mapped without access on Linux, adjacent readable segments are merged,
+    /// so patterns may cross their boundaries. The whole image is readable on Windows.
+    pub(super) fn readable_parts(&self) -> crate::Result<Vec<(usize, &'static [u8])>> {
+        let (base, end) = (
+            self.dll_base as usize,
+            self.dll_base as usize + self.image_size,
+        );
+
+        #[cfg(windows)]
+        let readable = [(base, end)];
+        #[cfg(target_os = "linux")]
+        let readable = crate::procfs::regions(std::process::id())?
+            .into_iter()
+            .filter(|r| r.is_readable())
+            .map(|r| (r.start, r.end));
+
+        Ok(merge_ranges(readable, base, end)
+            .into_iter()
+            .map(|(start, stop)| unsafe {
+                (
+                    start,
+                    std::slice::from_raw_parts(start as *const u8, stop - start),
+                )
+            })
+            .collect())
+    }
+}
+
+/// Clips sorted `ranges` to `base..end` and merges the adjacent ones.
+fn merge_ranges(
+    ranges: impl IntoIterator<Item = (usize, usize)>,
+    base: usize,
+    end: usize,
+) -> Vec<(usize, usize)> {
+    let mut merged: Vec<(usize, usize)> = vec![];
+    for (start, stop) in ranges {
+        if start >= end || stop <= base {
+            continue;
+        }
+        let (start, stop) = (start.max(base), stop.min(end));
+        match merged.last_mut() {
+            Some(last) if last.1 == start => last.1 = stop,
+            _ => merged.push((start, stop)),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::merge_ranges;
+
+    #[test]
+    fn adjacent_ranges_are_merged() {
+        let ranges = [
+            (0x0000, 0x1000),
+            (0x1000, 0x3000),
+            (0x3000, 0x4000),
+            // Gap without access.
+            (0x5000, 0x6000),
+            (0x6000, 0x7000),
+        ];
+        assert_eq!(
+            merge_ranges(ranges, 0x0000, 0x7000),
+            [(0x0000, 0x4000), (0x5000, 0x7000)]
+        );
+    }
+
+    #[test]
+    fn ranges_are_clipped_to_image() {
+        let ranges = [
+            (0x0000, 0x1000),
+            (0x1000, 0x3000),
+            (0x3000, 0x5000),
+            (0x6000, 0x8000),
+            (0x8000, 0x9000),
+        ];
+        assert_eq!(
+            merge_ranges(ranges, 0x2000, 0x7000),
+            [(0x2000, 0x5000), (0x6000, 0x7000)]
+        );
+        assert!(merge_ranges(ranges, 0x5000, 0x6000).is_empty());
+    }
+}
@@ -0,0 +1,91 @@
+use super::{get_module_information, ModuleInfo};
+use crate::pattern::{Pattern, PatternMatch, PatternSearcher, PatternSet, RichPattern};
+use std::ptr::NonNull;
+
+impl<'s> PatternSearcher<&'s PatternSet> for ModuleInfo {
+    type Output = (usize, usize);
+    type Iter = std::vec::IntoIter<(usize, usize)>;
+
+    /// Searches module's image for all patterns of the set in a single pass.
+    /// Yields `(pattern_index, address)`.
+    fn find_all(&self, set: &'s PatternSet) -> crate::Result<Self::Iter> {
+        Ok(self
+            .readable_parts()?
+            .into_iter()
+            .flat_map(|(base, part)| {
+                set.find_iter(part)
+                    .map(move |(i, offset)| (i, base + offset))
+            })
+            .collect::<Vec<_>>()
+            .into_iter())
+    }
+}
+
+impl<'p> PatternSearcher<&'p RichPattern> for ModuleInfo {
+    type Output = usize;
+    type Iter = std::vec::IntoIter<usize>;
+
+    /// Searches module's image for the pattern with jumps and alternatives.
+    fn find_all(&self, pat: &'p RichPattern) -> crate::Result<Self::Iter> {
+        Ok(self
+            .readable_parts()?
+            .into_iter()
+            .flat_map(|(base, part)| pat.find_iter(part).map(move |offset| base + offset))
+            .collect::<Vec<_>>()
+            .into_iter())
+    }
+}
+
+/// Searches module for specific memory pattern.
+/// With `rayon` feature enabled module's image is scanned by all threads of the pool.
+/// ```
+/// # use faithe::{internal::find_pattern, pattern::{Endian, Pattern}};
+/// static MARKER: [u8; 16] = *b"\x8Bfaithe\x00marker\xCC\xCC";
+///
+/// let exe = std::env::current_exe()?;
+/// let module = exe.file_name().unwrap().to_str().unwrap();
+/// let found = find_pattern(module, Pattern::from_value(MARKER, Endian::NATIVE))?.unwrap();
+/// assert_eq!(found.as_ptr() as *const u8, MARKER.as_ptr());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn find_pattern(mod_name: impl AsRef<str>, pat: Pattern) -> crate::Result<Option<NonNull<()>>> {
+    let info = get_module_information(mod_name)?;
+
+    Ok(info.readable_parts()?.into_iter().find_map(|(base, part)| {
+        #[cfg(feature = "rayon")]
+        let offset = pat.par_find_first(part);
+        #[cfg(not(feature = "rayon"))]
+        let offset = pat.find_iter(part).next();
+
+        offset.and_then(|offset| NonNull::new((base + offset) as _))
+    }))
+}
+
+/// Searches module for specific memory pattern and extracts its captured values.
+/// Relative captures are resolved against the address of the occurence.
+pub fn find_pattern_match(
+    mod_name: impl AsRef<str>,
+    pat: &Pattern,
+) -> crate::Result<Option<PatternMatch>> {
+    let info = get_module_information(mod_name)?;
+
+    Ok(info
+        .readable_parts()?
+        .into_iter()
+        .find_map(|(base, part)| pat.find_matches(part, base).next()))
+}
+
+/// Generates the shortest unique signature for the instruction at `address` inside of the module.
+/// On Linux the signature is unique within the contiguous readable part of the image that contains `address`.
+/// See [`SignatureBuilder`](crate::pattern::SignatureBuilder).
+#[cfg(feature = "iced-x86")]
+pub fn create_signature(mod_name: impl AsRef<str>, address: usize) -> crate::Result<Pattern> {
+    let info = get_module_information(mod_name)?;
+    let (base, image) = info
+        .readable_parts()?
+        .into_iter()
+        .find(|(base, part)| (*base..base + part.len()).contains(&address))
+        .unwrap_or((info.dll_base as usize, &[]));
+
+    crate::pattern::SignatureBuilder::new(image, base).build(address)
+}
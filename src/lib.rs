@@ -3,24 +3,41 @@
 #![doc = include_str!("../README.md")]
 
 /// APIs for internal interation with current process.
-#[cfg(not(feature = "no-std"))]
+#[cfg(all(any(windows, target_os = "linux"), not(feature = "no-std")))]
 pub mod internal;
 
 cfg_if::cfg_if! {
-    if #[cfg(all(feature = "external", not(feature = "no-std")))] {
+    if #[cfg(all(windows, feature = "external", not(feature = "no-std")))] {
         /// Iterator over threads and etc.
         pub mod thread;
         /// Module for doing common things with processes.
         pub mod process;
         /// Module for dealing with processs' modules.
         pub mod module;
+    } else if #[cfg(all(target_os = "linux", feature = "external", not(feature = "no-std")))] {
+        /// Iterator over threads.
+        pub mod thread;
+        /// Module for doing common things with processes.
+        pub mod process;
+        /// Module for dealing with processs' modules.
+        pub mod module;
     }
 }
 
+#[cfg(all(target_os = "linux", not(feature = "no-std")))]
+mod procfs;
+
 #[cfg(any(not(feature = "no-std"), feature = "alloc"))]
 /// Pattern searching.
 pub mod pattern;
 
+/// Declarative signature databases and their resolution.
+#[cfg(all(feature = "signatures", not(feature = "no-std")))]
+pub mod signatures;
+
+/// Platform-neutral access to memory of processes and buffers.
+pub mod memory;
+
 /// Re-exports of types used in windows.
 pub mod types;
 
@@ -30,6 +47,14 @@ pub use error::*;
 mod macros;
 pub use macros::*;
 
+#[doc(hidden)]
+#[cfg(all(
+    any(windows, target_os = "linux"),
+    feature = "inventory",
+    not(feature = "no-std")
+))]
+pub use inventory;
+
 /// Casts a pointer to an immutable reference.
 /// # Safety
 /// NO
@@ -53,7 +78,7 @@ pub unsafe fn to_mut_ref<'a, T>(ptr: *const T) -> &'a mut T {
 /// ```
 /// # use faithe::terminated_array;
 /// let arr: [u8; 4] = [1, 2, 3, 0];
-/// let terminated = unsafe { terminated_array(arr.as_ptr(), &0) };
+/// let terminated = unsafe { terminated_array(arr.as_ptr(), 0) };
 /// assert_eq!(terminated, &[1, 2, 3]);
 /// ```
 #[inline]
@@ -73,7 +98,7 @@ pub unsafe fn terminated_array<'a, T: PartialEq>(mut ptr: *const T, last: T) ->
 /// ```
 /// # use faithe::terminated_array_mut;
 /// let mut arr: [u8; 4] = [1, 2, 3, 0];
-/// let terminated = unsafe { terminated_array_mut(arr.as_mut_ptr(), &0) };
+/// let terminated = unsafe { terminated_array_mut(arr.as_mut_ptr(), 0) };
 /// assert_eq!(terminated, &[1, 2, 3]);
 /// terminated[1] = 5;
 /// assert_eq!(arr, [1, 5, 3, 0]);
@@ -95,7 +120,7 @@ pub unsafe fn terminated_array_mut<'a, T: PartialEq>(mut ptr: *mut T, last: T) -
 /// ```
 /// # use faithe::terminated_slice;
 /// let arr: [u8; 4] = [1, 2, 3, 0];
-/// let terminated = terminated_slice(&arr, &0);
+/// let terminated = terminated_slice(&arr, 0);
 /// assert_eq!(terminated, &[1, 2, 3]);
 /// ```
 pub fn terminated_slice<'a, T: PartialEq>(slice: &'a [T], last: T) -> &'a [T] {
@@ -113,7 +138,7 @@ pub fn terminated_slice<'a, T: PartialEq>(slice: &'a [T], last: T) -> &'a [T] {
 /// ```
 /// # use faithe::terminated_slice_mut;
 /// let mut arr: [u8; 4] = [1, 2, 3, 0];
-/// let terminated = terminated_slice_mut(&mut arr, &0);
+/// let terminated = terminated_slice_mut(&mut arr, 0);
 /// assert_eq!(terminated, &[1, 2, 3]);
 /// terminated[1] = 4;
 /// assert_eq!(terminated, &[1, 4, 3]);
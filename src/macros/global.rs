@@ -7,24 +7,82 @@
 /// // On `get` the value of type `i32` will be read at address `base("01-hello.exe") + 0x1234`.
 /// // COUNT also implements `AsRef` and `AsMut` traits but be careful because these methods can cause crash because they don't require unsafe block.
 /// assert_eq!(COUNT.get(), 123);
+///
+/// global! {
+///     // Located address can be processed by a chain of steps applied in order:
+///     // `rel32(n)` follows rel32 displacement at offset `n`, `deref` reads a pointer, `+n`/`-n` add to the address.
+///     extern PLAYER: usize = "01-hello.exe"@"E8 ? ? ? ? 48 8B 05" => rel32(1) => deref => +0x10;
+/// }
+/// // If any step fails, `FaitheError::StepFailed` tells its index and the reason.
+/// ```
+/// Address is located only once, even if many threads access the global at the same time.
+/// ```
+/// use std::collections::HashSet;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::{Arc, Barrier};
+///
+/// static THREADS: AtomicUsize = AtomicUsize::new(0);
+/// thread_local! {
+///     static ID: usize = THREADS.fetch_add(1, Ordering::Relaxed);
+/// }
+/// // Added to the address only when it's located,
+/// // so every thread that located the global again would get its own address.
+/// fn locator() -> usize {
+///     ID.with(|id| *id)
+/// }
+///
+/// // Image starts with its header, the global points right after the magic.
+/// #[cfg(windows)]
+/// faithe::global! {
+///     extern HEADER: u8 = "kernel32.dll"@"4D 5A"[(locator())] => +2;
+/// }
+/// #[cfg(target_os = "linux")]
+/// faithe::global! {
+///     extern HEADER: u8 = "libc.so.6"@"7F 45 4C 46"[(locator())] => +4;
+/// }
+/// let module = if cfg!(windows) { "kernel32.dll" } else { "libc.so.6" };
+/// let header = unsafe { &*std::ptr::addr_of!(HEADER) };
+///
+/// let barrier = Arc::new(Barrier::new(64));
+/// let threads = (0..64)
+///     .map(|_| {
+///         let barrier = barrier.clone();
+///         std::thread::spawn(move || {
+///             // Make all threads try to resolve the global at once.
+///             barrier.wait();
+///             unsafe { header.get_ref() as *const u8 as usize }
+///         })
+///     })
+///     .collect::<Vec<_>>();
+/// let addresses = threads
+///     .into_iter()
+///     .map(|t| t.join().unwrap())
+///     .collect::<HashSet<_>>();
+/// assert_eq!(addresses.len(), 1);
+///
+/// let start = faithe::internal::get_module_address(module)? as usize + if cfg!(windows) { 2 } else { 4 };
+/// assert!((start..start + 64).contains(addresses.iter().next().unwrap()));
+/// # Ok::<(), faithe::FaitheError>(())
 /// ```
 #[macro_export]
 macro_rules! global {
     (
         $(
-            $vs:vis extern $name:ident: $fty:ty = $lib_name:tt$sep:tt$var:tt$([$add:tt])?;
+            $vs:vis extern $name:ident: $fty:ty = $lib_name:tt$sep:tt$var:tt$([$add:tt])? $(=> $($step:ident)? $(+ $plus:literal)? $(- $minus:literal)? $(($arg:expr))?)*;
         )*
     ) => {
         $(
             #[allow(non_upper_case_globals)]
             $vs static mut $name: $name = $name {
-                offset: $crate::__define_offset!($sep $var)
+                offset: $crate::__define_offset!($sep $var, &[
+                    $($crate::__offset_step!($($step)? $(+ $plus)? $(- $minus)? $(($arg))?)),*
+                ])
             };
+            $crate::__register_offset!($name, $lib_name, $crate::__define_offset2!($($add)?), || unsafe { &*::core::ptr::addr_of!($name.offset) });
             #[allow(non_camel_case_types)]
             $vs struct $name {
                 offset: $crate::RuntimeOffset,
             }
-            unsafe impl ::core::marker::Sync for $name { }
             impl $name {
                 #[inline]
                 $vs unsafe fn get(&self) -> $fty {
@@ -33,20 +91,14 @@ macro_rules! global {
 
                 #[inline]
                 $vs unsafe fn get_ref(&self) -> &$fty {
-                    if !self.offset.is_resolved() {
-                        $crate::__expect!(self.offset.try_resolve($lib_name, $crate::__define_offset2!($($add)?)), "Failed to resolve global's address");
-                    }
-
-                    (self.offset.address() as *const $fty).as_ref().unwrap()
+                    let address = $crate::__expect!(self.offset.try_resolve($lib_name, $crate::__define_offset2!($($add)?)), "Failed to resolve global's address");
+                    (address as *const $fty).as_ref().unwrap()
                 }
 
                 #[inline]
                 $vs unsafe fn get_mut(&mut self) -> &mut $fty {
-                    if !self.offset.is_resolved() {
-                        $crate::__expect!(self.offset.try_resolve($lib_name, $crate::__define_offset2!($($add)?)), "Failed to resolve global's address");
-                    }
-
-                    (self.offset.address() as *mut $fty).as_mut().unwrap()
+                    let address = $crate::__expect!(self.offset.try_resolve($lib_name, $crate::__define_offset2!($($add)?)), "Failed to resolve global's address");
+                    (address as *mut $fty).as_mut().unwrap()
                 }
             }
         )*
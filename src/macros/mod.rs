@@ -4,6 +4,7 @@ mod sizeof;
 mod strings;
 mod vmt;
 mod parent;
+mod pattern;
 
 cfg_if::cfg_if! {
     if #[cfg(not(feature = "no-std"))] {
@@ -12,6 +13,19 @@ cfg_if::cfg_if! {
     }
 }
 
+#[cfg(all(
+    any(windows, target_os = "linux"),
+    feature = "inventory",
+    not(feature = "no-std")
+))]
+mod registry;
+#[cfg(all(
+    any(windows, target_os = "linux"),
+    feature = "inventory",
+    not(feature = "no-std")
+))]
+pub use registry::*;
+
 /// Macro for interal use. Provides functionality to hide panic messages if needed.
 #[doc(hidden)]
 #[macro_export]
@@ -25,117 +39,221 @@ macro_rules! __expect {
     };
 }
 
-#[cfg(not(feature = "no-std"))]
+#[cfg(all(any(windows, target_os = "linux"), not(feature = "no-std")))]
 enum InnerOffset {
     Explicit(usize),
-    Pattern(&'static str),
-    #[cfg(not(feature = "no-std"))]
-    Smart(&'static str),
-    Resolved(usize),
+    Pattern(crate::pattern::Pattern),
+    #[cfg(feature = "iced-x86")]
+    Smart(crate::pattern::Pattern),
+}
+
+/// Step applied to the located address of [`RuntimeOffset`], written after `=>` in [`crate::global!`] and [`crate::function!`].
+#[doc(hidden)]
+#[cfg(all(any(windows, target_os = "linux"), not(feature = "no-std")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OffsetStep {
+    /// `rel32(n)`, replaces the address with the one referred to by rel32 displacement located at the offset from it.
+    Rel32(usize),
+    /// `deref`, reads a pointer at the address.
+    Deref,
+    /// `+n` or `-n`, adds the value to the address.
+    Add(isize),
+}
+
+#[cfg(all(any(windows, target_os = "linux"), not(feature = "no-std")))]
+impl OffsetStep {
+    fn apply(self, address: usize) -> crate::Result<usize> {
+        use crate::memory::{CurrentProcess, MemoryReader};
+
+        Ok(match self {
+            Self::Rel32(offset) => {
+                let at = address.wrapping_add(offset);
+                let disp = CurrentProcess.read::<i32>(at)?;
+                at.wrapping_add(4).wrapping_add(disp as usize)
+            }
+            Self::Deref => CurrentProcess.read_pointer(address)?,
+            Self::Add(value) => address.wrapping_add(value as usize),
+        })
+    }
 }
 
+/// Offset that is located on the first use and cached, safe to resolve from several threads at once.
 #[doc(hidden)]
-#[cfg(not(feature = "no-std"))]
-pub struct RuntimeOffset(core::cell::UnsafeCell<InnerOffset>);
-#[cfg(not(feature = "no-std"))]
+#[cfg(all(any(windows, target_os = "linux"), not(feature = "no-std")))]
+pub struct RuntimeOffset {
+    source: InnerOffset,
+    steps: &'static [OffsetStep],
+    /// Set after `address` is written.
+    resolved: core::sync::atomic::AtomicBool,
+    address: core::sync::atomic::AtomicUsize,
+    /// Held while the offset is being located, so it's done only once.
+    lock: std::sync::Mutex<()>,
+}
+#[cfg(all(any(windows, target_os = "linux"), not(feature = "no-std")))]
 impl RuntimeOffset {
+    const fn new(source: InnerOffset, steps: &'static [OffsetStep]) -> Self {
+        Self {
+            source,
+            steps,
+            resolved: core::sync::atomic::AtomicBool::new(false),
+            address: core::sync::atomic::AtomicUsize::new(0),
+            lock: std::sync::Mutex::new(()),
+        }
+    }
+
     #[inline(always)]
     pub fn address(&self) -> usize {
-        unsafe {
-            match *(self.0.get()) {
-                InnerOffset::Resolved(address) => address,
-                _ => unreachable!(),
-            }
+        if self.is_resolved() {
+            self.address.load(core::sync::atomic::Ordering::Relaxed)
+        } else {
+            unreachable!()
         }
     }
 
     #[inline(always)]
     pub fn is_resolved(&self) -> bool {
-        unsafe { matches!(*(self.0.get()), InnerOffset::Resolved(_)) }
+        self.resolved.load(core::sync::atomic::Ordering::Acquire)
     }
 
+    /// Locates the offset unless it is already resolved and returns its address.
+    /// Concurrent calls wait for the first one and return the same address.
     #[inline]
-    pub fn try_resolve(&self, module: &'static str, add: usize) -> crate::Result<()> {
-        use crate::pattern::Pattern;
+    pub fn try_resolve(&self, module: &'static str, add: usize) -> crate::Result<usize> {
+        use core::sync::atomic::Ordering;
+
+        if self.is_resolved() {
+            return Ok(self.address());
+        }
+
+        // Poisoning doesn't matter, the state is only changed after the address is located.
+        let _guard = self
+            .lock
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if self.is_resolved() {
+            return Ok(self.address());
+        }
+
+        let address = self.locate(module, add)?;
+        self.address.store(address, Ordering::Relaxed);
+        self.resolved.store(true, Ordering::Release);
+        Ok(address)
+    }
+
+    fn locate(&self, module: &'static str, add: usize) -> crate::Result<usize> {
         use crate::FaitheError;
-        use iced_x86::{Decoder, DecoderOptions, Mnemonic, OpKind};
-
-        unsafe {
-            match *(self.0.get()) {
-                InnerOffset::Explicit(offset) => {
-                    let base = crate::internal::get_module_address(module)? as usize;
-                    *self.0.get() = InnerOffset::Resolved(base + offset + add);
-                    Ok(())
-                }
-                InnerOffset::Pattern(pat) => {
-                    let addr = crate::internal::find_pattern(module, Pattern::from_ida_style(pat))?
-                        .ok_or(FaitheError::PatternNotFound)?
-                        .as_ptr() as usize
-                        + add;
-                    *self.0.get() = InnerOffset::Resolved(addr);
-                    Ok(())
-                }
-                #[cfg(not(feature = "no-std"))]
-                InnerOffset::Smart(pat) => {
-                    let addr = crate::internal::find_pattern(module, Pattern::from_ida_style(pat))?
-                        .ok_or(FaitheError::PatternNotFound)?
-                        .as_ptr() as usize
-                        + add;
-                    let asm = crate::__expect!(
-                        Decoder::new(
-                            if cfg!(target_pointer_width = "64") {
-                                64
-                            } else {
-                                32
-                            },
-                            core::slice::from_raw_parts(addr as _, 15),
-                            DecoderOptions::NONE,
-                        )
-                        .iter()
-                        .next(),
-                        "Failed to disassemble instruction at address"
-                    );
-                    let end = match asm.mnemonic() {
-                        Mnemonic::Lea | Mnemonic::Mov if asm.op1_kind() == OpKind::Memory => {
-                            addr.wrapping_add(asm.memory_displacement64() as usize)
-                        }
-                        _ => unimplemented!(),
-                    };
-                    *self.0.get() = InnerOffset::Resolved(end);
-                    Ok(())
-                }
-                InnerOffset::Resolved(_) => Err(FaitheError::AlreadyResolved),
+
+        let mut address = match self.source {
+            InnerOffset::Explicit(offset) => {
+                crate::internal::get_module_address(module)? as usize + offset + add
             }
+            InnerOffset::Pattern(ref pat) => {
+                crate::internal::find_pattern(module, pat.clone())?
+                    .ok_or(FaitheError::PatternNotFound)?
+                    .as_ptr() as usize
+                    + add
+            }
+            #[cfg(feature = "iced-x86")]
+            InnerOffset::Smart(ref pat) => {
+                let addr = crate::internal::find_pattern(module, pat.clone())?
+                    .ok_or(FaitheError::PatternNotFound)?
+                    .as_ptr() as usize
+                    + add;
+                // Instructions are at most 15 bytes long.
+                let mut code = [0; 15];
+                let read = crate::internal::read_buf(addr, &mut code)?;
+                crate::pattern::instruction_target(&code[..read], addr, usize::BITS)?
+            }
+        };
+
+        for (i, step) in self.steps.iter().enumerate() {
+            address = step
+                .apply(address)
+                .map_err(|e| FaitheError::StepFailed(i, Box::new(e)))?;
         }
+        Ok(address)
     }
 
-    pub const fn explicit(offset: usize) -> Self {
-        Self(core::cell::UnsafeCell::new(InnerOffset::Explicit(offset)))
+    pub const fn explicit(offset: usize, steps: &'static [OffsetStep]) -> Self {
+        Self::new(InnerOffset::Explicit(offset), steps)
     }
 
-    pub const fn pattern(pat: &'static str) -> Self {
-        Self(core::cell::UnsafeCell::new(InnerOffset::Pattern(pat)))
+    pub const fn pattern(pat: crate::pattern::Pattern, steps: &'static [OffsetStep]) -> Self {
+        Self::new(InnerOffset::Pattern(pat), steps)
     }
 
-    pub const fn smart(pat: &'static str) -> Self {
-        Self(core::cell::UnsafeCell::new(InnerOffset::Smart(pat)))
+    #[cfg(feature = "iced-x86")]
+    pub const fn smart(pat: crate::pattern::Pattern, steps: &'static [OffsetStep]) -> Self {
+        Self::new(InnerOffset::Smart(pat), steps)
     }
 }
 
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __define_offset {
-    (# $var:tt) => {
-        $crate::RuntimeOffset::explicit($var)
+    (# $var:tt, $steps:expr) => {
+        $crate::RuntimeOffset::explicit($var, $steps)
+    };
+    (@ $var:tt, $steps:expr) => {
+        $crate::RuntimeOffset::pattern($crate::pattern!($var), $steps)
+    };
+    (% $var:tt, $steps:expr) => {
+        $crate::RuntimeOffset::smart($crate::pattern!($var), $steps)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __offset_step {
+    (+ $val:literal) => {
+        $crate::OffsetStep::Add($val as isize)
+    };
+    (- $val:literal) => {
+        $crate::OffsetStep::Add(-($val as isize))
+    };
+    (rel32 ($offset:expr)) => {
+        $crate::OffsetStep::Rel32($offset)
     };
-    (@ $var:tt) => {
-        $crate::RuntimeOffset::pattern($var)
+    (deref) => {
+        $crate::OffsetStep::Deref
     };
-    (% $var:tt) => {
-        $crate::RuntimeOffset::smart($var)
+    ($($step:tt)*) => {
+        ::core::compile_error!(::core::concat!("Unknown offset step: `", ::core::stringify!($($step)*), "`"))
     };
 }
 
+/// Adds the offset to the registry of [`resolve_all`].
+#[doc(hidden)]
+#[macro_export]
+#[cfg(all(
+    any(windows, target_os = "linux"),
+    feature = "inventory",
+    not(feature = "no-std")
+))]
+macro_rules! __register_offset {
+    ($name:ident, $module:expr, $add:expr, $offset:expr) => {
+        $crate::inventory::submit! {
+            $crate::OffsetRegistration {
+                name: ::core::concat!(::core::module_path!(), "::", ::core::stringify!($name)),
+                module: $module,
+                add: || $add,
+                offset: $offset,
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(all(
+    any(windows, target_os = "linux"),
+    feature = "inventory",
+    not(feature = "no-std")
+)))]
+macro_rules! __register_offset {
+    ($($tt:tt)*) => {};
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __define_offset2 {
@@ -18,10 +18,6 @@
 ///         pub b: i32
 ///     }
 /// }
-/// 
-/// struct Parent {
-///     
-/// }
 /// ```
 #[macro_export]
 macro_rules! parent {
@@ -0,0 +1,25 @@
+/// Creates [`crate::pattern::Pattern`] from IDA style pattern at compile time.
+/// Invalid patterns are reported as compile errors, created pattern is static and doesn't allocate.
+/// ```
+/// # use faithe::pattern;
+/// let pat = pattern!("48 8B 0? ? E8 $rel32");
+/// assert_eq!(pat.find_iter(&[0x48, 0x8B, 0x05, 0xFF, 0xE8, 0, 0, 0, 0]).next(), Some(0));
+/// assert_eq!(pat.captures().len(), 1);
+/// ```
+/// ```compile_fail
+/// # use faithe::pattern;
+/// let pat = pattern!("48 8G");
+/// ```
+#[macro_export]
+macro_rules! pattern {
+    ($pat:literal) => {{
+        const __SIZE: (usize, usize) = $crate::pattern::__pattern_size($pat);
+        const __PARSED: (
+            [$crate::pattern::ByteMatch; __SIZE.0],
+            [$crate::pattern::Capture; __SIZE.1],
+        ) = $crate::pattern::__pattern_parse::<{ __SIZE.0 }, { __SIZE.1 }>($pat);
+        const __PATTERN: $crate::pattern::Pattern =
+            $crate::pattern::Pattern::from_static(&__PARSED.0, &__PARSED.1);
+        __PATTERN
+    }};
+}
@@ -0,0 +1,124 @@
+use super::RuntimeOffset;
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+/// Offset declared with [`crate::function!`] or [`crate::global!`], collected for [`resolve_all`].
+#[doc(hidden)]
+pub struct OffsetRegistration {
+    pub name: &'static str,
+    pub module: &'static str,
+    /// Evaluated when the offset is resolved, the same way `function!` and `global!` do it.
+    pub add: fn() -> usize,
+    pub offset: fn() -> &'static RuntimeOffset,
+}
+
+inventory::collect!(OffsetRegistration);
+
+/// Result of resolving a single offset by [`resolve_all`].
+#[derive(Debug)]
+pub struct OffsetReport {
+    /// Path of the function or global, e.g. `my_crate::hooks::PLAYER`.
+    pub name: &'static str,
+    /// Module the offset is located in.
+    pub module: &'static str,
+    /// Resolved address or the reason of failure.
+    pub result: crate::Result<usize>,
+    /// Time it took to resolve the offset.
+    pub elapsed: Duration,
+}
+
+/// Report of [`resolve_all`], offsets are sorted by module and name.
+#[derive(Debug)]
+pub struct ResolveReport {
+    /// Every declared offset.
+    pub offsets: Vec<OffsetReport>,
+    /// Time it took to resolve all offsets.
+    pub elapsed: Duration,
+}
+
+impl ResolveReport {
+    /// Returns `true` if every offset was resolved.
+    pub fn is_ok(&self) -> bool {
+        self.offsets.iter().all(|o| o.result.is_ok())
+    }
+
+    /// Returns an iterator over the offsets that failed to resolve.
+    pub fn failed(&self) -> impl Iterator<Item = &OffsetReport> {
+        self.offsets.iter().filter(|o| o.result.is_err())
+    }
+}
+
+/// Prints a line per offset.
+impl fmt::Display for ResolveReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for o in &self.offsets {
+            write!(f, "{} [{}] ", o.name, o.module)?;
+            match &o.result {
+                Ok(address) => write!(f, "{:#X}", address)?,
+                Err(e) => write!(f, "failed: {}", e)?,
+            }
+            writeln!(f, " in {:?}", o.elapsed)?;
+        }
+        write!(
+            f,
+            "{}/{} resolved in {:?}",
+            self.offsets.len() - self.failed().count(),
+            self.offsets.len(),
+            self.elapsed
+        )
+    }
+}
+
+/// Resolves every offset declared with [`crate::function!`] and [`crate::global!`] in the whole program,
+/// so broken signatures show up at startup instead of on the first call.
+/// Offsets that are already resolved just report their address.
+/// ```
+/// #[cfg(windows)]
+/// faithe::global! {
+///     extern HEADER: u16 = "kernel32.dll"@"4D 5A";
+/// }
+/// #[cfg(target_os = "linux")]
+/// faithe::global! {
+///     extern HEADER: u32 = "libc.so.6"@"7F 45 4C 46";
+/// }
+/// faithe::global! {
+///     extern MISSING: u32 = "missing.dll"#0x1000;
+/// }
+///
+/// let report = faithe::resolve_all();
+/// assert!(!report.is_ok());
+/// assert_eq!(report.offsets.len(), 2);
+/// assert_eq!(report.failed().map(|o| o.name).collect::<Vec<_>>(), [concat!(module_path!(), "::MISSING")]);
+///
+/// let module = if cfg!(windows) { "kernel32.dll" } else { "libc.so.6" };
+/// let base = faithe::internal::get_module_address(module)? as usize;
+/// let text = report.to_string();
+/// assert!(text.contains(&format!("::HEADER [{}] {:#X} in ", module, base)));
+/// assert!(text.contains("::MISSING [missing.dll] failed: ModuleNotFound in "));
+/// assert!(text.lines().last().unwrap().starts_with("1/2 resolved in "));
+/// # Ok::<(), faithe::FaitheError>(())
+/// ```
+pub fn resolve_all() -> ResolveReport {
+    let start = Instant::now();
+    let mut offsets = inventory::iter::<OffsetRegistration>
+        .into_iter()
+        .map(|r| {
+            let start = Instant::now();
+            let result = (r.offset)().try_resolve(r.module, (r.add)());
+            OffsetReport {
+                name: r.name,
+                module: r.module,
+                result,
+                elapsed: start.elapsed(),
+            }
+        })
+        .collect::<Vec<_>>();
+    offsets.sort_by_key(|o| (o.module, o.name));
+
+    ResolveReport {
+        offsets,
+        elapsed: start.elapsed(),
+    }
+}
@@ -2,7 +2,7 @@
 /// # Behaviour
 /// Each time macro is used, it will create new virtual method table via [`Box::leak`].
 /// ```
-/// # use faithe::vmt
+/// # use faithe::vmt;
 /// fn first() {
 ///     println!("First");
 /// }
@@ -10,10 +10,16 @@
 ///     println!("Second");
 /// }
 ///
-/// let vmt = vmt! {
-///     first,
-///     second
-/// };
+/// #[repr(C)]
+/// struct Object {
+///     vmt: usize,
+/// }
+///
+/// let object = Object { vmt: 0 };
+/// unsafe {
+///     vmt!(object => [first, second]);
+/// }
+/// assert_ne!(object.vmt, 0);
 /// ```
 #[macro_export]
 macro_rules! vmt {
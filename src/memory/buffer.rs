@@ -0,0 +1,48 @@
+use super::{MemoryReader, MemoryWriter};
+use crate::FaitheError;
+
+/// Bytes located at some address, e.g. a dump of a process's memory.
+/// Memory outside of the bytes can't be read or written.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct MemoryBuffer<B> {
+    /// Address of the first byte.
+    pub base: usize,
+    /// Contents of the memory.
+    pub data: B,
+}
+
+impl<B> MemoryBuffer<B> {
+    /// Creates new buffer which first byte is located at `base`.
+    pub fn new(base: usize, data: B) -> Self {
+        Self { base, data }
+    }
+
+    /// Returns offset of the address from the start of the data or an error if it's outside of `len` bytes.
+    fn offset(&self, address: usize, len: usize) -> crate::Result<usize> {
+        address
+            .checked_sub(self.base)
+            .filter(|offset| *offset < len)
+            .ok_or(FaitheError::QueryFailed)
+    }
+}
+
+impl<B: AsRef<[u8]>> MemoryReader for MemoryBuffer<B> {
+    fn read_bytes(&self, address: usize, buf: &mut [u8]) -> crate::Result<usize> {
+        let data = self.data.as_ref();
+        let data = &data[self.offset(address, data.len())?..];
+        let len = data.len().min(buf.len());
+        buf[..len].copy_from_slice(&data[..len]);
+        Ok(len)
+    }
+}
+
+impl<B: AsMut<[u8]>> MemoryWriter for MemoryBuffer<B> {
+    fn write_bytes(&mut self, address: usize, buf: &[u8]) -> crate::Result<usize> {
+        let len = self.data.as_mut().len();
+        let offset = self.offset(address, len)?;
+        let data = &mut self.data.as_mut()[offset..];
+        let len = data.len().min(buf.len());
+        data[..len].copy_from_slice(&buf[..len]);
+        Ok(len)
+    }
+}
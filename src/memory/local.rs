@@ -0,0 +1,47 @@
+use super::{MemoryReader, MemoryWriter};
+
+/// The process the code is running in.
+/// Memory is queried before it's accessed, so reading or writing inaccessible pages fails instead of crashing.
+/// ```
+/// # use faithe::memory::{CurrentProcess, MemoryReader, MemoryWriter};
+/// let mut value = 5u32;
+/// let address = &mut value as *mut u32 as usize;
+/// CurrentProcess.write(address, 10u32)?;
+/// assert_eq!(CurrentProcess.read::<u32>(address)?, 10);
+/// assert!(CurrentProcess.read::<u32>(0).is_err());
+/// # Ok::<(), faithe::FaitheError>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CurrentProcess;
+
+impl MemoryReader for CurrentProcess {
+    #[inline]
+    fn read_bytes(&self, address: usize, buf: &mut [u8]) -> crate::Result<usize> {
+        crate::internal::read_buf(address, buf)
+    }
+}
+
+impl MemoryWriter for CurrentProcess {
+    #[inline]
+    fn write_bytes(&mut self, address: usize, buf: &[u8]) -> crate::Result<usize> {
+        crate::internal::write_buf(address, buf)
+    }
+}
+
+#[cfg(feature = "external")]
+impl super::MemoryQuery for CurrentProcess {
+    #[inline]
+    fn query_region(&self, address: usize) -> crate::Result<super::MemoryRegion> {
+        #[cfg(windows)]
+        return crate::internal::query(address).map(Into::into);
+        #[cfg(target_os = "linux")]
+        return crate::internal::query(address);
+    }
+
+    /// Regions are listed from `/proc/self/maps` at once.
+    #[cfg(target_os = "linux")]
+    fn regions(&self) -> crate::process::MemoryRegionIter<'_, Self> {
+        let regions = crate::procfs::regions(std::process::id()).unwrap_or_default();
+        crate::process::MemoryRegionIter::listed(self, regions)
+    }
+}
@@ -0,0 +1,198 @@
+use crate::FaitheError;
+
+mod buffer;
+pub use buffer::*;
+
+#[cfg(not(feature = "no-std"))]
+mod region;
+#[cfg(not(feature = "no-std"))]
+pub use region::*;
+
+#[cfg(all(any(windows, target_os = "linux"), not(feature = "no-std")))]
+mod local;
+#[cfg(all(any(windows, target_os = "linux"), not(feature = "no-std")))]
+pub use local::*;
+
+/// Plain old data that can be read from any bytes and written as bytes.
+/// # Safety
+/// Every bit pattern of `size_of::<Self>()` bytes must be a valid value of the type and the type must have no padding.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($ty:ty),*) => {
+        $(unsafe impl Pod for $ty {})*
+    };
+}
+
+impl_pod!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+/// Memory that can be read, e.g. address space of the current or another process or a memory dump.
+/// ```
+/// # use faithe::memory::{MemoryBuffer, MemoryReader};
+/// let mut data = vec![0; 0x30];
+/// // Pointer at 0x1000 leads to 0x1010, pointer at 0x1018 leads to 0x1020.
+/// data[0x00..0x08].copy_from_slice(&0x1010u64.to_ne_bytes());
+/// data[0x18..0x20].copy_from_slice(&0x1020u64.to_ne_bytes());
+/// data[0x24..0x28].copy_from_slice(&1337u32.to_ne_bytes());
+/// let mem = MemoryBuffer::new(0x1000, data);
+///
+/// assert_eq!(mem.read::<u32>(0x1024)?, 1337);
+/// let address = mem.follow_pointer_path(0x1000, &[0, 8, 4])?;
+/// assert_eq!(address, 0x1024);
+/// assert!(mem.read::<u64>(0x102C).is_err());
+/// # Ok::<(), faithe::FaitheError>(())
+/// ```
+pub trait MemoryReader {
+    /// Reads memory at `address` into `buf` and returns the amount of bytes read.
+    /// Reading stops at the first byte that can't be read,
+    /// returns an error or `0` if memory at `address` can't be read.
+    fn read_bytes(&self, address: usize, buf: &mut [u8]) -> crate::Result<usize>;
+
+    /// Fills the whole `buf` with memory at `address`.
+    /// Fails with [`FaitheError::QueryFailed`] if only a part of it could be read.
+    fn read_exact(&self, address: usize, buf: &mut [u8]) -> crate::Result<()> {
+        if self.read_bytes(address, buf)? == buf.len() {
+            Ok(())
+        } else {
+            Err(FaitheError::QueryFailed)
+        }
+    }
+
+    /// Reads value of type `T` at `address`.
+    fn read<T: Pod>(&self, address: usize) -> crate::Result<T>
+    where
+        Self: Sized,
+    {
+        // Pod can be made of any bytes, zeroes included.
+        let mut value: T = unsafe { core::mem::zeroed() };
+        let buf = unsafe {
+            core::slice::from_raw_parts_mut(
+                &mut value as *mut T as *mut u8,
+                core::mem::size_of::<T>(),
+            )
+        };
+        self.read_exact(address, buf)?;
+        Ok(value)
+    }
+
+    /// Reads pointer at `address`.
+    fn read_pointer(&self, address: usize) -> crate::Result<usize> {
+        let mut buf = [0; core::mem::size_of::<usize>()];
+        self.read_exact(address, &mut buf)?;
+        Ok(usize::from_ne_bytes(buf))
+    }
+
+    /// Follows multilevel pointer: every offset but the last is added to the address and a pointer is read there,
+    /// the last one is only added, so the address of the value is returned.
+    fn follow_pointer_path(&self, mut base: usize, offsets: &[usize]) -> crate::Result<usize> {
+        if let Some((last, path)) = offsets.split_last() {
+            for offset in path {
+                base = self.read_pointer(base.wrapping_add(*offset))?;
+            }
+            base = base.wrapping_add(*last);
+        }
+        Ok(base)
+    }
+}
+
+impl<R: MemoryReader + ?Sized> MemoryReader for &R {
+    #[inline]
+    fn read_bytes(&self, address: usize, buf: &mut [u8]) -> crate::Result<usize> {
+        (**self).read_bytes(address, buf)
+    }
+}
+
+impl<R: MemoryReader + ?Sized> MemoryReader for &mut R {
+    #[inline]
+    fn read_bytes(&self, address: usize, buf: &mut [u8]) -> crate::Result<usize> {
+        (**self).read_bytes(address, buf)
+    }
+}
+
+/// Memory that can be written.
+/// ```
+/// # use faithe::memory::{MemoryBuffer, MemoryReader, MemoryWriter};
+/// let mut mem = MemoryBuffer::new(0x1000, [0u8; 0x10]);
+/// mem.write(0x1004, 0xDEADBEEFu32)?;
+/// assert_eq!(mem.read::<u32>(0x1004)?, 0xDEADBEEF);
+/// assert_eq!(mem.data[4..8], 0xDEADBEEFu32.to_ne_bytes());
+/// assert!(mem.write(0x100E, 0u32).is_err());
+/// # Ok::<(), faithe::FaitheError>(())
+/// ```
+pub trait MemoryWriter {
+    /// Writes `buf` into memory at `address` and returns the amount of bytes written.
+    /// Writing stops at the first byte that can't be written,
+    /// returns an error or `0` if memory at `address` can't be written.
+    fn write_bytes(&mut self, address: usize, buf: &[u8]) -> crate::Result<usize>;
+
+    /// Writes the whole `buf` into memory at `address`.
+    /// Fails with [`FaitheError::QueryFailed`] if only a part of it could be written.
+    fn write_all(&mut self, address: usize, buf: &[u8]) -> crate::Result<()> {
+        if self.write_bytes(address, buf)? == buf.len() {
+            Ok(())
+        } else {
+            Err(FaitheError::QueryFailed)
+        }
+    }
+
+    /// Writes value of type `T` at `address`.
+    fn write<T: Pod>(&mut self, address: usize, value: T) -> crate::Result<()>
+    where
+        Self: Sized,
+    {
+        let buf = unsafe {
+            core::slice::from_raw_parts(&value as *const T as *const u8, core::mem::size_of::<T>())
+        };
+        self.write_all(address, buf)
+    }
+}
+
+impl<W: MemoryWriter + ?Sized> MemoryWriter for &mut W {
+    #[inline]
+    fn write_bytes(&mut self, address: usize, buf: &[u8]) -> crate::Result<usize> {
+        (**self).write_bytes(address, buf)
+    }
+}
+
+/// Memory which regions can be listed, e.g. address space of a process.
+#[cfg(all(
+    any(windows, target_os = "linux"),
+    feature = "external",
+    not(feature = "no-std")
+))]
+pub trait MemoryQuery {
+    /// Returns the region that contains `address`, free regions included.
+    fn query_region(&self, address: usize) -> crate::Result<MemoryRegion>;
+
+    /// Returns an iterator over allocated regions.
+    fn regions(&self) -> crate::process::MemoryRegionIter<'_, Self>
+    where
+        Self: Sized,
+    {
+        crate::process::MemoryRegionIter::new(self)
+    }
+
+    /// Returns continuous readable parts of memory from `from` to `to`.
+    /// Uncommitted, guard and no access pages are skipped.
+    fn readable_ranges(&self, from: usize, to: usize) -> Vec<core::ops::Range<usize>> {
+        let mut ranges: Vec<core::ops::Range<usize>> = vec![];
+        let mut address = from;
+        while address < to {
+            let region = match self.query_region(address) {
+                Ok(region) => region,
+                Err(_) => break,
+            };
+            let end = region.end.min(to);
+            if region.is_readable() {
+                match ranges.last_mut() {
+                    Some(last) if last.end == address => last.end = end,
+                    _ => ranges.push(address..end),
+                }
+            }
+            address = end;
+        }
+        ranges
+    }
+}
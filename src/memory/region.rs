@@ -0,0 +1,117 @@
+use crate::types::MemoryProtection;
+
+/// What memory region is used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RegionKind {
+    /// Memory that isn't allocated.
+    Free,
+    /// Executable or shared library.
+    /// On Linux every mapping of a file that has an executable mapping as well.
+    Image,
+    /// File mapped into memory that isn't an executable image.
+    Mapped,
+    /// Heap of the process, `[heap]` on Linux.
+    Heap,
+    /// Stack of the main thread, `[stack]` on Linux.
+    Stack,
+    /// Memory shared with the kernel, `[vdso]`, `[vvar]` and `[vsyscall]` on Linux.
+    Vdso,
+    /// Private memory that isn't backed by a file.
+    /// Windows doesn't tell heaps and stacks apart from other private memory.
+    Anonymous,
+}
+
+/// Allocated memory region.
+#[derive(Debug, Clone)]
+pub struct MemoryRegion {
+    /// Start of the region
+    pub start: usize,
+    /// End of the region
+    pub end: usize,
+    /// Size of the region
+    pub size: usize,
+    /// Base address of the allocation the region belongs to, base address of the module for images.
+    pub allocation_base: usize,
+    /// Protection of the region, `NONE` for uncommitted and guard pages.
+    pub protection: MemoryProtection,
+    /// Changes are visible to other processes mapping the same object.
+    /// Always `false` on Windows.
+    pub shared: bool,
+    /// Offset of the region inside of the mapped file.
+    /// Always `0` on Windows.
+    pub offset: u64,
+    /// Inode of the mapped file.
+    /// Always `0` on Windows.
+    pub inode: u64,
+    /// Path of the mapped file.
+    /// Always `None` on Windows.
+    pub path: Option<String>,
+    /// What the region is used for.
+    pub kind: RegionKind,
+    /// Amount of bytes of the region that are in RAM, only known for regions from `/proc/pid/smaps`.
+    pub rss: Option<usize>,
+    /// Amount of modified bytes of the region, only known for regions from `/proc/pid/smaps`.
+    pub dirty: Option<usize>,
+    /// Initial protection of the region
+    #[cfg(windows)]
+    pub initial: MemoryProtection,
+    /// State of the region's pages
+    #[cfg(windows)]
+    pub state: windows::Win32::System::Memory::VIRTUAL_ALLOCATION_TYPE,
+}
+
+impl MemoryRegion {
+    /// Returns `true` if region is committed and its memory can be read.
+    #[inline]
+    pub fn is_readable(&self) -> bool {
+        self.protection.contains(MemoryProtection::READ)
+    }
+}
+
+#[cfg(windows)]
+impl From<crate::types::MemoryBasicInformation> for MemoryRegion {
+    fn from(mbi: crate::types::MemoryBasicInformation) -> Self {
+        use windows::Win32::System::Memory::{
+            MEM_COMMIT, MEM_FREE, MEM_IMAGE, MEM_MAPPED, PAGE_EXECUTE_WRITECOPY, PAGE_GUARD,
+            PAGE_PROTECTION_FLAGS, PAGE_WRITECOPY,
+        };
+
+        let protection = |flags: PAGE_PROTECTION_FLAGS| {
+            // Modifiers like `PAGE_GUARD` and `PAGE_NOCACHE` are in the upper bits.
+            let flags = PAGE_PROTECTION_FLAGS(flags.0 & 0xFF);
+            let prot = MemoryProtection::from_os(flags).unwrap_or(MemoryProtection::NONE);
+            // Copy-on-write pages can be read too.
+            if flags == PAGE_WRITECOPY || flags == PAGE_EXECUTE_WRITECOPY {
+                prot | MemoryProtection::READ
+            } else {
+                prot
+            }
+        };
+
+        Self {
+            start: mbi.base_address,
+            end: mbi.base_address + mbi.region_size,
+            size: mbi.region_size,
+            allocation_base: mbi.alloc_base,
+            protection: if mbi.state != MEM_COMMIT || mbi.protection.0 & PAGE_GUARD.0 != 0 {
+                MemoryProtection::NONE
+            } else {
+                protection(mbi.protection)
+            },
+            shared: false,
+            offset: 0,
+            inode: 0,
+            path: None,
+            kind: match (mbi.state, mbi.memory_type) {
+                (MEM_FREE, _) => RegionKind::Free,
+                (_, MEM_IMAGE) => RegionKind::Image,
+                (_, MEM_MAPPED) => RegionKind::Mapped,
+                _ => RegionKind::Anonymous,
+            },
+            rss: None,
+            dirty: None,
+            initial: protection(mbi.alloc_protection),
+            state: mbi.state,
+        }
+    }
+}
@@ -0,0 +1,20 @@
+#[cfg(windows)]
+use windows::Win32::Foundation::HINSTANCE;
+
+/// Represents a single module in a running process.
+#[derive(Debug, Clone)]
+pub struct ModuleEntry {
+    /// Id of the process.
+    pub process_id: u32,
+    /// Base address of the module.
+    pub base_address: usize,
+    /// Size of the module in bytes.
+    pub size: usize,
+    /// Handle to the module.
+    #[cfg(windows)]
+    pub handle: HINSTANCE,
+    /// Name of the module.
+    pub name: String,
+    /// Full path to the module.
+    pub path: String,
+}
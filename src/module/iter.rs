@@ -1,30 +1,14 @@
+use super::ModuleEntry;
 use crate::FaitheError;
 use std::mem::size_of;
 use windows::Win32::{
-    Foundation::{HANDLE, HINSTANCE},
+    Foundation::HANDLE,
     System::Diagnostics::ToolHelp::{
         CreateToolhelp32Snapshot, Module32FirstW, Module32NextW, MODULEENTRY32W, TH32CS_SNAPMODULE,
         TH32CS_SNAPMODULE32,
     },
 };
 
-/// Represents a single module in a running process.
-#[derive(Debug, Clone)]
-pub struct ModuleEntry {
-    /// Id of the process.
-    pub process_id: u32,
-    /// Base address of the module.
-    pub base_address: usize,
-    /// Size of the module in bytes.
-    pub size: usize,
-    /// Handle to the module.
-    pub handle: HINSTANCE,
-    /// Name of the module.
-    pub name: String,
-    /// Full path to the module.
-    pub path: String,
-}
-
 impl From<MODULEENTRY32W> for ModuleEntry {
     fn from(me: MODULEENTRY32W) -> Self {
         Self {
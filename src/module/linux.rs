@@ -0,0 +1,65 @@
+use super::ModuleEntry;
+use crate::procfs;
+use std::path::Path;
+
+/// Iterator over process's loaded modules.
+/// Modules are file-backed mappings from `/proc/pid/maps` grouped by path,
+/// in the order they are mapped in.
+/// ```
+/// # use faithe::module::ModuleIterator;
+/// let exe = std::env::current_exe()?;
+/// let module = ModuleIterator::new(std::process::id())?
+///     .find(|m| m.path == exe.to_str().unwrap())
+///     .unwrap();
+/// assert_eq!(module.name, exe.file_name().unwrap().to_str().unwrap());
+/// // Executable starts with ELF header.
+/// assert_eq!(unsafe { *(module.base_address as *const [u8; 4]) }, *b"\x7FELF");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct ModuleIterator {
+    modules: std::vec::IntoIter<ModuleEntry>,
+}
+
+impl ModuleIterator {
+    /// Creates new iterator over modules of process with id `process_id`
+    pub fn new(process_id: u32) -> crate::Result<Self> {
+        let mut modules: Vec<ModuleEntry> = vec![];
+        for map in procfs::maps(process_id)? {
+            // Device files like GPU memory are mapped too, they aren't modules.
+            if !map.is_file() || map.path.starts_with("/dev/") {
+                continue;
+            }
+
+            match modules.iter_mut().find(|m| m.path == map.path) {
+                Some(module) => {
+                    let end = (module.base_address + module.size).max(map.end);
+                    module.base_address = module.base_address.min(map.start);
+                    module.size = end - module.base_address;
+                }
+                None => modules.push(ModuleEntry {
+                    process_id,
+                    base_address: map.start,
+                    size: map.end - map.start,
+                    name: Path::new(&map.path)
+                        .file_name()
+                        .map(|n| n.to_string_lossy().into_owned())
+                        .unwrap_or_default(),
+                    path: map.path,
+                }),
+            }
+        }
+
+        Ok(Self {
+            modules: modules.into_iter(),
+        })
+    }
+}
+
+impl Iterator for ModuleIterator {
+    type Item = ModuleEntry;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.modules.next()
+    }
+}
@@ -1,5 +1,15 @@
-mod iter;
-pub use iter::*;
+mod entry;
+pub use entry::*;
+
+cfg_if::cfg_if! {
+    if #[cfg(windows)] {
+        mod iter;
+        pub use iter::*;
+    } else {
+        mod linux;
+        pub use linux::*;
+    }
+}
 
 mod pat;
 pub use pat::*;
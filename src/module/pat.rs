@@ -1,51 +1,29 @@
 use super::ModuleEntry;
 use crate::{
-    pattern::{Pattern, PatternSearcher},
+    pattern::{ChunkFindIter, ChunkScanner, Pattern, PatternSearcher, PatternSet, RichPattern},
     process::OwnedProcess,
+    types::access_rights::PROCESS_VM_READ,
 };
-use windows::Win32::System::Threading::PROCESS_VM_READ;
 
 /// Iterator over module pattern occurences.
-pub struct ModulePatIter {
-    proc: OwnedProcess,
-    pat: Pattern,
-    from: usize,
-    to: usize,
-    buf: Box<[u8]>,
-}
+/// Module's memory is read in big chunks, unreadable pages are skipped.
+pub struct ModulePatIter(ChunkFindIter<OwnedProcess>);
 
 impl ModulePatIter {
     pub(crate) fn new(pid: u32, from: usize, to: usize, pat: Pattern) -> crate::Result<Self> {
         let proc = OwnedProcess::open_by_id(pid, false, PROCESS_VM_READ)?;
+        let ranges = proc.readable_ranges(from, to);
 
-        Ok(Self {
-            proc,
-            from,
-            to,
-            buf: vec![0; pat.len()].into_boxed_slice(),
-            pat,
-        })
+        Ok(Self(ChunkScanner::new(proc).find_iter(pat, ranges)))
     }
 }
 
 impl Iterator for ModulePatIter {
     type Item = usize;
 
+    #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        if self.from > self.to - self.pat.len() {
-            None
-        } else {
-            loop {
-                if let Err(_) = self.proc.read_buf(self.from, &mut self.buf[..]) {
-                    return None;
-                }
-
-                if self.pat.matches(&self.buf) {
-                    break Some(self.from);
-                }
-                self.from += 1;
-            }
-        }
+        self.0.next()
     }
 }
 
@@ -61,4 +39,41 @@ impl PatternSearcher for ModuleEntry {
             pat,
         )
     }
+
+    /// Chunks of module's memory are read and scanned by all threads of the pool,
+    /// chunks after the first occurence are cancelled.
+    #[cfg(feature = "rayon")]
+    fn find_first(&self, pat: Pattern) -> crate::Result<Option<usize>> {
+        let proc = OwnedProcess::open_by_id(self.process_id, false, PROCESS_VM_READ)?;
+        let ranges = proc.readable_ranges(self.base_address, self.base_address + self.size);
+
+        Ok(ChunkScanner::new(proc).par_find_first(&pat, ranges))
+    }
+}
+
+impl<'s> PatternSearcher<&'s PatternSet> for ModuleEntry {
+    type Output = (usize, usize);
+    type Iter = ChunkFindIter<OwnedProcess, &'s PatternSet>;
+
+    /// Searches module's memory for all patterns of the set in a single pass.
+    /// Yields `(pattern_index, address)`.
+    fn find_all(&self, set: &'s PatternSet) -> crate::Result<Self::Iter> {
+        let proc = OwnedProcess::open_by_id(self.process_id, false, PROCESS_VM_READ)?;
+        let ranges = proc.readable_ranges(self.base_address, self.base_address + self.size);
+
+        Ok(ChunkScanner::new(proc).find_iter(set, ranges))
+    }
+}
+
+impl<'p> PatternSearcher<&'p RichPattern> for ModuleEntry {
+    type Output = usize;
+    type Iter = ChunkFindIter<OwnedProcess, &'p RichPattern>;
+
+    /// Searches module's memory for the pattern with jumps and alternatives.
+    fn find_all(&self, pat: &'p RichPattern) -> crate::Result<Self::Iter> {
+        let proc = OwnedProcess::open_by_id(self.process_id, false, PROCESS_VM_READ)?;
+        let ranges = proc.readable_ranges(self.base_address, self.base_address + self.size);
+
+        Ok(ChunkScanner::new(proc).find_iter(pat, ranges))
+    }
 }
@@ -0,0 +1,199 @@
+extern crate alloc;
+use super::{ByteMatch, Pattern, PatternSearcher};
+use alloc::{borrow::Cow, vec::Vec};
+
+/// The maximum amount of bytes [`Pattern::analyze`] appends to the pattern trying to make it unique.
+const MAX_EXTENSION: usize = 64;
+
+/// Occurences of the pattern in a single image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageReport {
+    /// Offsets of all occurences from the start of the image.
+    pub matches: Vec<usize>,
+}
+
+impl ImageReport {
+    /// Returns the amount of occurences.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.matches.len()
+    }
+}
+
+/// Change of the pattern that makes it unique in every analyzed image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Suggestion {
+    /// Pattern is already unique and can't be made shorter.
+    Keep,
+    /// Shorter pattern that is still unique everywhere.
+    Shrink(Pattern),
+    /// Longer pattern that is unique everywhere.
+    Extend(Pattern),
+    /// Pattern can't be made unique by shrinking or extending it.
+    Unfixable,
+}
+
+/// Result of [`Pattern::analyze`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternReport {
+    /// Occurences of the pattern in every image, in the same order images were supplied.
+    pub images: Vec<ImageReport>,
+    /// Offsets inside of the pattern where matched bytes are not the same for all occurences.
+    /// Only wildcarded bytes can be listed, these are the ones that really change between builds.
+    pub differing: Vec<usize>,
+    /// Suggested change of the pattern.
+    pub suggestion: Suggestion,
+}
+
+impl PatternReport {
+    /// Returns `true` if pattern matches exactly once in every image.
+    pub fn is_unique(&self) -> bool {
+        self.images.iter().all(|i| i.count() == 1)
+    }
+}
+
+impl Pattern {
+    /// Checks how robust the pattern is against several module images, e.g. archived builds of the same game.
+    /// Reports the occurences in every image, bytes that differ between occurences
+    /// and a suggested pattern that is unique in all images.
+    ///
+    /// When the pattern has to be extended, bytes following the first occurence in the first image are appended.
+    /// Bytes that are not the same in the closest occurences of the other images are wildcarded.
+    /// ```
+    /// # use faithe::pattern::{Pattern, Suggestion};
+    /// let old = [0x55, 0x8B, 0xEC, 0xE8, 1, 0, 0, 0, 0xC3, 0x55, 0x8B, 0xEC, 0x90];
+    /// let new = [0x90, 0x55, 0x8B, 0xEC, 0xE8, 2, 0, 0, 0, 0xC3, 0x55, 0x8B, 0xEC, 0xCC];
+    ///
+    /// let report = Pattern::from_ida_style("55 8B EC").analyze(&[&old[..], &new[..]]);
+    /// assert_eq!(report.images[0].matches, [0, 9]);
+    /// assert_eq!(report.images[1].matches, [1, 10]);
+    /// assert!(!report.is_unique());
+    /// assert_eq!(
+    ///     report.suggestion,
+    ///     Suggestion::Extend(Pattern::from_ida_style("55 8B EC E8"))
+    /// );
+    ///
+    /// let report = Pattern::from_ida_style("EC E8 ? ? ? ? C3").analyze(&[&old[..], &new[..]]);
+    /// assert!(report.is_unique());
+    /// assert_eq!(report.differing, [2]);
+    /// assert_eq!(
+    ///     report.suggestion,
+    ///     Suggestion::Shrink(Pattern::from_ida_style("EC E8"))
+    /// );
+    /// ```
+    pub fn analyze<I: AsRef<[u8]>>(&self, images: &[I]) -> PatternReport {
+        let images = images.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+        let matches = images
+            .iter()
+            .map(|data| self.find_iter(data).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let differing = (0..self.len())
+            .filter(|i| {
+                let mut values = images
+                    .iter()
+                    .zip(&matches)
+                    .flat_map(|(data, offsets)| offsets.iter().map(move |o| data[o + i]));
+                let first = values.next();
+                values.any(|v| Some(v) != first)
+            })
+            .collect();
+
+        PatternReport {
+            suggestion: self.suggest(&images, &matches),
+            images: matches
+                .into_iter()
+                .map(|matches| ImageReport { matches })
+                .collect(),
+            differing,
+        }
+    }
+
+    /// Counts occurences of the pattern using each of `searchers`, e.g. modules of several running processes.
+    pub fn match_counts<S: PatternSearcher>(
+        &self,
+        searchers: impl IntoIterator<Item = S>,
+    ) -> crate::Result<Vec<usize>> {
+        searchers
+            .into_iter()
+            .map(|s| Ok(s.find_all(self.clone())?.count()))
+            .collect()
+    }
+
+    /// Creates a pattern with different bytes, keeping the captures that still fit.
+    fn with_bytes(&self, bytes: Vec<ByteMatch>) -> Self {
+        Self {
+            captures: self
+                .captures
+                .iter()
+                .filter(|c| c.offset + c.len <= bytes.len())
+                .copied()
+                .collect::<Vec<_>>()
+                .into(),
+            bytes: Cow::Owned(bytes),
+        }
+    }
+
+    fn suggest(&self, images: &[&[u8]], matches: &[Vec<usize>]) -> Suggestion {
+        if images.is_empty() {
+            return Suggestion::Unfixable;
+        }
+        let counts = |bytes: &[ByteMatch]| {
+            let pat = Self::from(bytes.to_vec());
+            images
+                .iter()
+                .map(|data| pat.find_iter(data).take(2).count())
+                .collect::<Vec<_>>()
+        };
+
+        // The amount of occurences only decreases as the pattern grows,
+        // so the first prefix that matches at most once is the only candidate.
+        for len in 1..=self.len() {
+            if self.bytes[len - 1].is_any() {
+                continue;
+            }
+            let counts = counts(&self.bytes[..len]);
+            if counts.iter().all(|c| *c <= 1) {
+                return if counts.contains(&0) {
+                    Suggestion::Unfixable
+                } else if len == self.len() {
+                    Suggestion::Keep
+                } else {
+                    Suggestion::Shrink(self.with_bytes(self.bytes[..len].to_vec()))
+                };
+            }
+        }
+        if matches.iter().any(Vec::is_empty) {
+            return Suggestion::Unfixable;
+        }
+
+        let following = |image: usize, offset: usize| {
+            let data = images[image];
+            let start = (offset + self.len()).min(data.len());
+            &data[start..(start + MAX_EXTENSION).min(data.len())]
+        };
+        let reference = following(0, matches[0][0]);
+        let closest = (1..images.len())
+            .map(|i| {
+                matches[i]
+                    .iter()
+                    .map(|o| following(i, *o))
+                    .max_by_key(|f| f.iter().zip(reference).filter(|(a, b)| a == b).count())
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+
+        let mut bytes = self.bytes.to_vec();
+        for (i, b) in reference.iter().enumerate() {
+            if closest.iter().all(|f| f.get(i) == Some(b)) {
+                bytes.push(ByteMatch::exact(*b));
+                if counts(&bytes).iter().all(|c| *c == 1) {
+                    return Suggestion::Extend(self.with_bytes(bytes));
+                }
+            } else {
+                bytes.push(ByteMatch::ANY);
+            }
+        }
+        Suggestion::Unfixable
+    }
+}
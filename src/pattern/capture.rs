@@ -0,0 +1,178 @@
+extern crate alloc;
+use super::Pattern;
+use alloc::vec::Vec;
+
+/// How captured bytes are interpreted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CaptureKind {
+    /// Raw bytes, captured with `[` and `]`.
+    Bytes,
+    /// Little-endian unsigned integer, captured with `$u8`, `$u16`, `$u32` or `$u64`.
+    Unsigned,
+    /// Little-endian signed integer, captured with `$i8`, `$i16`, `$i32` or `$i64`.
+    Signed,
+    /// Displacement relative to the end of the captured bytes, captured with `$rel8` or `$rel32`.
+    /// Resolved into an absolute address.
+    Relative,
+}
+
+/// Slot of the pattern which bytes are extracted on match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Capture {
+    /// Offset of the first captured byte from the start of the pattern.
+    pub offset: usize,
+    /// Amount of captured bytes.
+    pub len: usize,
+    /// Interpretation of captured bytes.
+    pub kind: CaptureKind,
+}
+
+impl Capture {
+    fn extract(&self, data: &[u8], address: usize) -> Captured {
+        let bytes = &data[self.offset..self.offset + self.len];
+        match self.kind {
+            CaptureKind::Bytes => Captured::Bytes(bytes.to_vec()),
+            CaptureKind::Unsigned => Captured::Unsigned(read_le(bytes)),
+            CaptureKind::Signed => Captured::Signed(sign_extend(read_le(bytes), self.len)),
+            CaptureKind::Relative => Captured::Address(
+                (address + self.offset + self.len)
+                    .wrapping_add(sign_extend(read_le(bytes), self.len) as usize),
+            ),
+        }
+    }
+}
+
+fn read_le(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .rev()
+        .fold(0, |value, b| value << 8 | *b as u64)
+}
+
+fn sign_extend(value: u64, len: usize) -> i64 {
+    let shift = 64 - len as u32 * 8;
+    ((value << shift) as i64) >> shift
+}
+
+/// Value extracted from the matched memory.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Captured {
+    /// Raw bytes.
+    Bytes(Vec<u8>),
+    /// Unsigned integer.
+    Unsigned(u64),
+    /// Signed integer.
+    Signed(i64),
+    /// Absolute address resolved from relative displacement.
+    Address(usize),
+}
+
+impl Captured {
+    /// Returns captured value as an integer. Raw bytes are read as little-endian integer.
+    pub fn as_u64(&self) -> u64 {
+        match self {
+            Self::Bytes(b) => read_le(&b[..b.len().min(8)]),
+            Self::Unsigned(v) => *v,
+            Self::Signed(v) => *v as u64,
+            Self::Address(a) => *a as u64,
+        }
+    }
+
+    /// Returns resolved address if capture was relative.
+    pub fn as_address(&self) -> Option<usize> {
+        match self {
+            Self::Address(a) => Some(*a),
+            _ => None,
+        }
+    }
+}
+
+/// Single pattern occurence with all of its captured values.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PatternMatch {
+    /// Address (or offset) of the first byte of the occurence.
+    pub address: usize,
+    /// Captured values in order of their appearance in the pattern.
+    pub captures: Vec<Captured>,
+}
+
+impl PatternMatch {
+    /// Returns captured value with index `idx`.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<&Captured> {
+        self.captures.get(idx)
+    }
+
+    /// Resolves rip-relative displacement stored in capture `idx`, for instructions that don't end right after
+    /// the displacement (e.g. `cmp [rip + disp32], imm8`).
+    /// `instruction_end` is an offset of the end of the instruction from the start of the match.
+    /// ```
+    /// # use faithe::pattern::Pattern;
+    /// // cmp byte ptr [rip + 0x10], 0
+    /// let pat = Pattern::from_ida_style("80 3D [? ? ? ?] 00");
+    /// let found = pat.match_at(&[0x80, 0x3D, 0x10, 0, 0, 0, 0], 0x1000).unwrap();
+    /// assert_eq!(found.rip_relative(0, 7), Some(0x1017));
+    /// ```
+    pub fn rip_relative(&self, idx: usize, instruction_end: usize) -> Option<usize> {
+        let disp = match self.get(idx)? {
+            Captured::Bytes(b) => sign_extend(read_le(b), b.len()),
+            Captured::Signed(v) => *v,
+            Captured::Unsigned(v) => *v as i32 as i64,
+            Captured::Address(_) => return None,
+        };
+        Some((self.address + instruction_end).wrapping_add(disp as usize))
+    }
+}
+
+impl Pattern {
+    /// Returns capture slots of the pattern.
+    #[inline]
+    pub fn captures(&self) -> &[Capture] {
+        &self.captures
+    }
+
+    /// Checks if `data` starts with the pattern and extracts captured values.
+    /// `address` is the address of `data` used to resolve relative captures.
+    /// ```
+    /// # use faithe::pattern::{Captured, Pattern};
+    /// let pat = Pattern::from_ida_style("E8 $rel32 8B 05 [? ?] ? ?");
+    /// let data = [0xE8, 0xFB, 0xFF, 0xFF, 0xFF, 0x8B, 0x05, 0xAA, 0xBB, 0, 0];
+    /// let found = pat.match_at(&data, 0x1000).unwrap();
+    /// assert_eq!(found.get(0), Some(&Captured::Address(0x1000)));
+    /// assert_eq!(found.get(1), Some(&Captured::Bytes(vec![0xAA, 0xBB])));
+    /// ```
+    pub fn match_at(&self, data: &[u8], address: usize) -> Option<PatternMatch> {
+        let data = data.get(..self.len())?;
+        if !self.matches(data) {
+            return None;
+        }
+
+        Some(PatternMatch {
+            address,
+            captures: self
+                .captures
+                .iter()
+                .map(|c| c.extract(data, address))
+                .collect(),
+        })
+    }
+
+    /// Returns an iterator over all occurences of the pattern in `data` with their captured values.
+    /// `base` is the address of `data`, occurences' addresses are relative to it.
+    /// ```
+    /// # use faithe::pattern::Pattern;
+    /// let pat = Pattern::from_ida_style("E8 $rel32");
+    /// let data = [0x90, 0xE8, 0x10, 0, 0, 0];
+    /// let found = pat.find_matches(&data, 0x1000).next().unwrap();
+    /// assert_eq!(found.address, 0x1001);
+    /// assert_eq!(found.captures[0].as_address(), Some(0x1016));
+    /// ```
+    pub fn find_matches<'a>(
+        &'a self,
+        data: &'a [u8],
+        base: usize,
+    ) -> impl Iterator<Item = PatternMatch> + 'a {
+        self.find_iter(data)
+            .filter_map(move |offset| self.match_at(&data[offset..], base + offset))
+    }
+}
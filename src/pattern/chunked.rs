@@ -0,0 +1,254 @@
+extern crate alloc;
+use super::{Pattern, PatternSet};
+use crate::memory::MemoryReader;
+use alloc::{collections::VecDeque, vec, vec::Vec};
+use core::ops::Range;
+
+const PAGE_SIZE: usize = 0x1000;
+
+/// Something that can be searched for chunk by chunk, i.e. a [`Pattern`] or a [`PatternSet`].
+pub trait Needle {
+    /// Value reported for every occurence.
+    type Output;
+
+    /// Length of the longest possible occurence.
+    fn max_len(&self) -> usize;
+
+    /// Appends occurences in `data` located at `address` to `out` in the order of their addresses.
+    fn find_in(&self, data: &[u8], address: usize, out: &mut Vec<Self::Output>);
+
+    /// Returns the address of the occurence.
+    fn address(out: &Self::Output) -> usize;
+}
+
+impl<N: Needle + ?Sized> Needle for &N {
+    type Output = N::Output;
+
+    #[inline]
+    fn max_len(&self) -> usize {
+        (**self).max_len()
+    }
+
+    #[inline]
+    fn find_in(&self, data: &[u8], address: usize, out: &mut Vec<Self::Output>) {
+        (**self).find_in(data, address, out)
+    }
+
+    #[inline]
+    fn address(out: &Self::Output) -> usize {
+        N::address(out)
+    }
+}
+
+impl Needle for Pattern {
+    type Output = usize;
+
+    #[inline]
+    fn max_len(&self) -> usize {
+        self.len()
+    }
+
+    fn find_in(&self, data: &[u8], address: usize, out: &mut Vec<usize>) {
+        out.extend(self.find_iter(data).map(|o| address + o));
+    }
+
+    #[inline]
+    fn address(out: &usize) -> usize {
+        *out
+    }
+}
+
+impl Needle for PatternSet {
+    /// `(pattern_index, address)`
+    type Output = (usize, usize);
+
+    #[inline]
+    fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    fn find_in(&self, data: &[u8], address: usize, out: &mut Vec<(usize, usize)>) {
+        out.extend(self.find_iter(data).map(|(i, o)| (i, address + o)));
+    }
+
+    #[inline]
+    fn address(out: &(usize, usize)) -> usize {
+        out.1
+    }
+}
+
+/// Scans memory that can only be read in chunks, e.g. address space of another process.
+/// Neighbouring chunks overlap, so occurences crossing their boundaries are found too.
+/// Unreadable pages are skipped.
+/// ```
+/// # use faithe::{memory::MemoryReader, pattern::{ChunkScanner, Pattern}};
+/// /// Memory at `0x10000` where the second page is not readable.
+/// struct Holey(Vec<u8>);
+///
+/// impl MemoryReader for Holey {
+///     fn read_bytes(&self, address: usize, buf: &mut [u8]) -> Result<usize, faithe::FaitheError> {
+///         let (start, end) = (address - 0x10000, address - 0x10000 + buf.len());
+///         if start < 0x2000 && end > 0x1000 {
+///             return Err(faithe::FaitheError::QueryFailed);
+///         }
+///         buf.copy_from_slice(&self.0[start..end]);
+///         Ok(buf.len())
+///     }
+/// }
+///
+/// let mut mem = vec![0; 0x4000];
+/// // Crosses the boundary of the chunks.
+/// mem[0x2FFE..0x3002].copy_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+/// // Lies in unreadable page.
+/// mem[0x1100..0x1104].copy_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+///
+/// let found = ChunkScanner::new(Holey(mem))
+///     .chunk_size(0x1000)
+///     .find_iter(Pattern::from_ida_style("DE AD ? EF"), [0x10000..0x14000])
+///     .collect::<Vec<_>>();
+/// assert_eq!(found, [0x12FFE]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChunkScanner<R> {
+    pub(super) reader: R,
+    pub(super) chunk_size: usize,
+}
+
+impl<R: MemoryReader> ChunkScanner<R> {
+    /// Creates new scanner that reads memory in chunks of 1 MiB.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            chunk_size: 0x100000,
+        }
+    }
+
+    /// Sets the size of chunks. Chunks are never smaller than a page.
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(PAGE_SIZE);
+        self
+    }
+
+    /// Returns an iterator over all occurences of the needle in `ranges` of memory, ordered by address.
+    pub fn find_iter<N: Needle>(
+        self,
+        needle: N,
+        ranges: impl IntoIterator<Item = Range<usize>>,
+    ) -> ChunkFindIter<R, N> {
+        ChunkFindIter {
+            scanner: self,
+            needle,
+            ranges: ranges.into_iter().collect::<Vec<_>>().into_iter(),
+            current: 0..0,
+            buf: vec![],
+            buf_start: 0,
+            page_mode_until: 0,
+            tentative: vec![],
+            ready: VecDeque::new(),
+        }
+    }
+}
+
+/// Iterator over occurences of the needle in memory read in chunks.
+/// Created by [`ChunkScanner::find_iter`].
+pub struct ChunkFindIter<R, N: Needle = Pattern> {
+    scanner: ChunkScanner<R>,
+    needle: N,
+    ranges: alloc::vec::IntoIter<Range<usize>>,
+    /// Part of the current range that wasn't read yet.
+    current: Range<usize>,
+    /// Last chunk with the tail of the previous one in front of it.
+    buf: Vec<u8>,
+    /// Address of the first byte of `buf`.
+    buf_start: usize,
+    /// After a failed read of a whole chunk its memory is read page by page up to this address.
+    page_mode_until: usize,
+    /// Occurences that start in the part of the buffer carried over to the next chunk.
+    /// If the next chunk continues the buffer they are searched for again with more data available,
+    /// otherwise they are final.
+    tentative: Vec<N::Output>,
+    ready: VecDeque<N::Output>,
+}
+
+impl<R: MemoryReader, N: Needle> ChunkFindIter<R, N> {
+    /// Drops the buffer, so that the next chunk doesn't continue it.
+    fn break_run(&mut self) {
+        self.ready.extend(self.tentative.drain(..));
+        self.buf.clear();
+    }
+
+    fn read_next(&mut self) {
+        let overlap = self.needle.max_len().saturating_sub(1);
+        let address = self.current.start;
+        if self.buf_start + self.buf.len() != address {
+            self.break_run();
+            self.buf_start = address;
+        }
+
+        // Keep the tail of the previous chunk, so occurences crossing the boundary are found.
+        let carried = self.buf.len().min(overlap);
+        self.buf.drain(..self.buf.len() - carried);
+        self.buf_start = address - carried;
+
+        let len = if address < self.page_mode_until {
+            PAGE_SIZE - address % PAGE_SIZE
+        } else {
+            self.scanner.chunk_size
+        }
+        .min(self.current.end - address);
+
+        self.buf.resize(carried + len, 0);
+        match self
+            .scanner
+            .reader
+            .read_bytes(address, &mut self.buf[carried..])
+        {
+            Ok(read) if read != 0 => {
+                self.buf.truncate(carried + read);
+                self.current.start += read;
+
+                let mut found = core::mem::take(&mut self.tentative);
+                found.clear();
+                self.needle.find_in(&self.buf, self.buf_start, &mut found);
+
+                let next_start = self.buf_start + self.buf.len() - overlap.min(self.buf.len());
+                let tentative = found.partition_point(|o| N::address(o) < next_start);
+                self.tentative = found.split_off(tentative);
+                self.ready.extend(found);
+            }
+            _ => {
+                self.buf.truncate(carried);
+                if address >= self.page_mode_until && len > PAGE_SIZE - address % PAGE_SIZE {
+                    // Some page of the chunk is unreadable, find out which one.
+                    self.page_mode_until = address + len;
+                } else {
+                    self.current.start = (address / PAGE_SIZE + 1) * PAGE_SIZE;
+                    self.current.start = self.current.start.min(self.current.end);
+                    self.break_run();
+                }
+            }
+        }
+    }
+}
+
+impl<R: MemoryReader, N: Needle> Iterator for ChunkFindIter<R, N> {
+    type Item = N::Output;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(found) = self.ready.pop_front() {
+                return Some(found);
+            }
+
+            if self.current.is_empty() {
+                match self.ranges.next() {
+                    Some(range) => self.current = range,
+                    None if !self.tentative.is_empty() => self.break_run(),
+                    None => return None,
+                }
+            } else {
+                self.read_next();
+            }
+        }
+    }
+}
@@ -0,0 +1,225 @@
+extern crate alloc;
+use super::{ByteMatch, Capture, CaptureKind, Pattern};
+use crate::FaitheError;
+use alloc::{string::String, vec::Vec};
+use core::{
+    fmt::{self, Write},
+    str::FromStr,
+};
+
+impl Capture {
+    /// Returns the name of the typed capture (without `$`) or `None` if it can only be written as a group.
+    fn type_name(&self) -> Option<&'static str> {
+        Some(match (self.kind, self.len) {
+            (CaptureKind::Unsigned, 1) => "u8",
+            (CaptureKind::Unsigned, 2) => "u16",
+            (CaptureKind::Unsigned, 4) => "u32",
+            (CaptureKind::Unsigned, 8) => "u64",
+            (CaptureKind::Signed, 1) => "i8",
+            (CaptureKind::Signed, 2) => "i16",
+            (CaptureKind::Signed, 4) => "i32",
+            (CaptureKind::Signed, 8) => "i64",
+            (CaptureKind::Relative, 1) => "rel8",
+            (CaptureKind::Relative, 4) => "rel32",
+            _ => return None,
+        })
+    }
+}
+
+pub(super) fn write_byte(out: &mut String, m: ByteMatch, wildcard: &str) {
+    // Writing into a `String` never fails.
+    let _ = match m.mask() {
+        0x00 => {
+            out.push_str(wildcard);
+            Ok(())
+        }
+        0xFF => write!(out, "{:02X}", m.value()),
+        0xF0 => write!(out, "{:X}?", m.value() >> 4),
+        0x0F => write!(out, "?{:X}", m.value()),
+        mask => write!(out, "{:02X}&{:02X}", m.value(), mask),
+    };
+}
+
+impl Pattern {
+    fn to_text(&self, wildcard: &str) -> String {
+        let mut out = String::new();
+        // Captures that overlap with previous ones can't be written and are skipped.
+        let mut captures = self
+            .captures
+            .iter()
+            .filter(|c| c.len != 0 && c.offset + c.len <= self.len())
+            .peekable();
+        let mut group_end = None;
+
+        let mut i = 0;
+        while i < self.len() {
+            if !out.is_empty() {
+                out.push(' ');
+            }
+
+            let mut typed = None;
+            if group_end.is_none() {
+                while let Some(c) = captures.next_if(|c| c.offset <= i) {
+                    if c.offset == i {
+                        typed = Some(c);
+                        break;
+                    }
+                }
+            }
+            if let Some(c) = typed {
+                match c.type_name() {
+                    Some(name) if self.bytes[i..i + c.len].iter().all(ByteMatch::is_any) => {
+                        out.push('$');
+                        out.push_str(name);
+                        i += c.len;
+                        continue;
+                    }
+                    _ => {
+                        out.push('[');
+                        group_end = Some(i + c.len);
+                    }
+                }
+            }
+
+            write_byte(&mut out, self.bytes[i], wildcard);
+            i += 1;
+            if group_end == Some(i) {
+                out.push(']');
+                group_end = None;
+            }
+        }
+        out
+    }
+
+    /// Formats the pattern in IDA style, the same syntax [`Pattern::from_ida_style`] accepts.
+    /// ```
+    /// # use faithe::pattern::Pattern;
+    /// let pat = Pattern::from_ida_style("48 8B 0? [?5 E8] $rel32 C0&F0 ?");
+    /// assert_eq!(pat.to_ida_style(), "48 8B 0? [?5 E8] $rel32 C? ?");
+    /// ```
+    pub fn to_ida_style(&self) -> String {
+        self.to_text("?")
+    }
+
+    /// Formats the pattern in PEiD style, the same syntax [`Pattern::from_peid_style`] accepts.
+    /// ```
+    /// # use faithe::pattern::Pattern;
+    /// let pat = Pattern::from_ida_style("48 8B ? ? 90");
+    /// assert_eq!(pat.to_peid_style(), "48 8B ?? ?? 90");
+    /// ```
+    pub fn to_peid_style(&self) -> String {
+        self.to_text("??")
+    }
+
+    /// Formats the pattern in code style, returns bytes and mask the same way [`Pattern::from_code_style`] accepts them.
+    /// Code style has no partial wildcards, so bytes that are only partially checked are turned into wildcards.
+    /// Captures are omitted.
+    /// ```
+    /// # use faithe::pattern::Pattern;
+    /// let (bytes, mask) = Pattern::from_ida_style("48 8B ? E?").to_code_style();
+    /// assert_eq!(bytes, b"\x48\x8B\x00\x00");
+    /// assert_eq!(mask, "xx??");
+    /// ```
+    pub fn to_code_style(&self) -> (Vec<u8>, String) {
+        self.bytes
+            .iter()
+            .map(|m| {
+                if m.is_exact() {
+                    (m.value(), 'x')
+                } else {
+                    (0, '?')
+                }
+            })
+            .unzip()
+    }
+}
+
+/// Formats the pattern in IDA style, alternate flag (`{:#}`) switches to PEiD style.
+/// ```
+/// # use faithe::pattern::Pattern;
+/// let pat = Pattern::from_ida_style("E8 $rel32 ? 90");
+/// assert_eq!(format!("{}", pat), "E8 $rel32 ? 90");
+/// assert_eq!(format!("{:#}", pat), "E8 $rel32 ?? 90");
+/// ```
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_text(if f.alternate() { "??" } else { "?" }))
+    }
+}
+
+/// Parses the pattern detecting its style.
+/// Code style is written as escaped bytes followed by the mask: `\x48\x8B\x00 xx?`.
+/// Anything else is parsed as IDA style, which accepts PEiD style too.
+/// ```
+/// # use faithe::pattern::Pattern;
+/// let ida: Pattern = "48 8B ? 90".parse().unwrap();
+/// let peid: Pattern = "48 8B ?? 90".parse().unwrap();
+/// let code: Pattern = r"\x48\x8B\x00\x90 xx?x".parse().unwrap();
+/// assert_eq!(ida, peid);
+/// assert_eq!(ida, code);
+///
+/// let pat = Pattern::from_ida_style("48 8B 0? [?5 E8] $rel32 C0&F0 ?");
+/// assert_eq!(pat.to_string().parse::<Pattern>().unwrap(), pat);
+/// assert_eq!(pat.to_peid_style().parse::<Pattern>().unwrap(), pat);
+/// ```
+impl FromStr for Pattern {
+    type Err = FaitheError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if !s.starts_with("\\x") {
+            return Self::try_from_ida_style(s);
+        }
+
+        let (bytes, mask) = s
+            .split_once(|c: char| c.is_ascii_whitespace())
+            .ok_or(FaitheError::PatternMaskMismatch)?;
+        let bytes = bytes
+            .split("\\x")
+            .skip(1)
+            .map(|b| u8::from_str_radix(b, 16).map_err(|_| FaitheError::InvalidPattern))
+            .collect::<crate::Result<Vec<_>>>()?;
+        if mask.trim().bytes().any(|m| m != b'x' && m != b'?') {
+            return Err(FaitheError::InvalidPattern);
+        }
+        Self::try_from_code_style(&bytes, mask.trim().as_bytes())
+    }
+}
+
+/// Patterns are serialized as IDA style strings.
+/// ```
+/// # use faithe::pattern::Pattern;
+/// let pat = Pattern::from_ida_style("48 8B 05 $rel32");
+/// let json = serde_json::to_string(&pat).unwrap();
+/// assert_eq!(json, r#""48 8B 05 $rel32""#);
+/// assert_eq!(serde_json::from_str::<Pattern>(&json).unwrap(), pat);
+/// ```
+#[cfg(feature = "serde")]
+impl serde::Serialize for Pattern {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_ida_style())
+    }
+}
+
+/// Patterns are deserialized from strings of any style [`FromStr`] accepts.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Pattern {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = Pattern;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a pattern string")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.parse()
+                    .map_err(|_| E::invalid_value(serde::de::Unexpected::Str(v), &self))
+            }
+        }
+
+        deserializer.deserialize_str(Visitor)
+    }
+}
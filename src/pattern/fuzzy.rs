@@ -0,0 +1,169 @@
+extern crate alloc;
+use super::{ByteMatch, Pattern};
+use alloc::vec::Vec;
+
+/// Occurence of the pattern found by [`Pattern::find_fuzzy`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FuzzyMatch {
+    /// Offset of the occurence from the start of the data.
+    pub offset: usize,
+    /// Offsets inside of the pattern of the bytes that didn't match.
+    pub mismatches: Vec<usize>,
+}
+
+impl FuzzyMatch {
+    /// Returns the amount of bytes that didn't match.
+    #[inline]
+    pub fn mismatch_count(&self) -> usize {
+        self.mismatches.len()
+    }
+
+    /// Returns `true` if every byte of the pattern matched.
+    #[inline]
+    pub fn is_exact(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+
+    /// Returns the pattern with mismatched bytes wildcarded, so that it matches this occurence exactly.
+    /// Captures are kept.
+    pub fn fixed(&self, pat: &Pattern) -> Pattern {
+        let mut bytes = pat.bytes.to_vec();
+        for i in &self.mismatches {
+            bytes[*i] = ByteMatch::ANY;
+        }
+
+        Pattern {
+            bytes: bytes.into(),
+            captures: pat.captures.clone(),
+        }
+    }
+}
+
+impl Pattern {
+    /// Returns offsets inside of the pattern of the bytes that don't match `data`,
+    /// or `None` if there are more than `max_mismatches` of them.
+    fn mismatches(&self, data: &[u8], max_mismatches: usize) -> Option<Vec<usize>> {
+        let mut mismatches = Vec::new();
+        for (i, (b, m)) in data.iter().zip(self.bytes.iter()).enumerate() {
+            if !m.matches(*b) {
+                if mismatches.len() == max_mismatches {
+                    return None;
+                }
+                mismatches.push(i);
+            }
+        }
+        Some(mismatches)
+    }
+
+    /// Finds occurences of the pattern in `data` that have at most `max_mismatches` non-wildcard bytes not matching.
+    /// Results are ranked by the amount of mismatches, then by offset, so exact occurences come first.
+    ///
+    /// Useful when a patch has changed a byte of otherwise stable signature:
+    /// the best candidate tells which bytes have to be wildcarded, see [`FuzzyMatch::fixed`].
+    /// ```
+    /// # use faithe::pattern::Pattern;
+    /// let pat = Pattern::from_ida_style("48 8B 05 ? ? ? ? 48 85 C0");
+    /// // `48 85 C0` became `48 85 C9` after the update.
+    /// let data = [0x90, 0x48, 0x8B, 0x05, 1, 2, 3, 4, 0x48, 0x85, 0xC9, 0xC3];
+    /// assert_eq!(pat.find_iter(&data).next(), None);
+    ///
+    /// let found = pat.find_fuzzy(&data, 1);
+    /// assert_eq!(found.len(), 1);
+    /// assert_eq!(found[0].offset, 1);
+    /// assert_eq!(found[0].mismatches, [9]);
+    ///
+    /// let fixed = found[0].fixed(&pat);
+    /// assert_eq!(fixed, Pattern::from_ida_style("48 8B 05 ? ? ? ? 48 85 ?"));
+    /// assert_eq!(fixed.find_iter(&data).next(), Some(1));
+    /// ```
+    /// Results are always the same as of checking every position one by one:
+    /// ```
+    /// # use faithe::pattern::{ByteMatch, Pattern};
+    /// let mut seed = 0x9E3779B97F4A7C15u64;
+    /// let mut rand = move || {
+    ///     seed ^= seed << 13;
+    ///     seed ^= seed >> 7;
+    ///     seed ^= seed << 17;
+    ///     seed
+    /// };
+    /// for _ in 0..300 {
+    ///     let data = (0..rand() % 256).map(|_| (rand() % 4) as u8).collect::<Vec<u8>>();
+    ///     let bytes = (0..1 + rand() % 8)
+    ///         .map(|_| match rand() % 5 {
+    ///             0 => ByteMatch::ANY,
+    ///             _ => ByteMatch::exact((rand() % 4) as u8),
+    ///         })
+    ///         .collect::<Vec<_>>();
+    ///     let k = (rand() % 4) as usize;
+    ///
+    ///     let mut naive = data
+    ///         .windows(bytes.len())
+    ///         .enumerate()
+    ///         .map(|(i, w)| {
+    ///             let mismatches = w.iter().zip(&bytes).filter(|(b, m)| !m.matches(**b)).count();
+    ///             (mismatches, i)
+    ///         })
+    ///         .filter(|(mismatches, _)| *mismatches <= k)
+    ///         .collect::<Vec<_>>();
+    ///     naive.sort();
+    ///
+    ///     let pat = bytes.iter().copied().collect::<Pattern>();
+    ///     let found = pat
+    ///         .find_fuzzy(&data, k)
+    ///         .into_iter()
+    ///         .map(|m| (m.mismatch_count(), m.offset))
+    ///         .collect::<Vec<_>>();
+    ///     assert_eq!(naive, found, "{:?} {}", pat, k);
+    /// }
+    /// ```
+    pub fn find_fuzzy(&self, data: &[u8], max_mismatches: usize) -> Vec<FuzzyMatch> {
+        if self.len() == 0 || self.len() > data.len() {
+            return Vec::new();
+        }
+
+        let checked = self
+            .bytes
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| !m.is_any())
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+
+        let candidates = if checked.len() <= max_mismatches {
+            // Every position is within the budget.
+            (0..=data.len() - self.len()).collect::<Vec<_>>()
+        } else {
+            // With `k` mismatches at least one of `k + 1` pieces of the pattern matches exactly,
+            // so only positions where some piece is found have to be checked.
+            let pieces = max_mismatches + 1;
+            let mut candidates = Vec::new();
+            for piece in 0..pieces {
+                let start = match piece {
+                    0 => 0,
+                    _ => checked[piece * checked.len() / pieces],
+                };
+                let end = match checked.get((piece + 1) * checked.len() / pieces) {
+                    Some(end) if piece + 1 < pieces => *end,
+                    _ => self.len(),
+                };
+
+                let sub = self.bytes[start..end].iter().copied().collect::<Pattern>();
+                let tail = self.len() - end;
+                candidates.extend(sub.find_iter(&data[start..data.len() - tail]));
+            }
+            candidates.sort_unstable();
+            candidates.dedup();
+            candidates
+        };
+
+        let mut found = candidates
+            .into_iter()
+            .filter_map(|offset| {
+                self.mismatches(&data[offset..offset + self.len()], max_mismatches)
+                    .map(|mismatches| FuzzyMatch { offset, mismatches })
+            })
+            .collect::<Vec<_>>();
+        found.sort_by_key(|m| (m.mismatch_count(), m.offset));
+        found
+    }
+}
@@ -0,0 +1,364 @@
+use super::{Needle, PatternSearcher};
+use crate::{memory::MemoryReader, FaitheError};
+use memmap2::Mmap;
+use std::{fs::File, ops::Range, path::Path};
+
+/// Section of the executable image that is loaded into memory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    /// Name of the section, e.g. `.text`.
+    pub name: String,
+    /// Offset of the section's data from the start of the file.
+    pub offset: usize,
+    /// Size of the section's data in the file.
+    pub size: usize,
+    /// Virtual address of the section when image is loaded at its preferred base.
+    pub address: usize,
+}
+
+impl Section {
+    /// Range of the section's data in the file, `None` if it overflows.
+    fn file_range(&self) -> Option<Range<usize>> {
+        Some(self.offset..self.offset.checked_add(self.size)?)
+    }
+
+    /// Range of the section's virtual addresses, `None` if it overflows.
+    fn address_range(&self) -> Option<Range<usize>> {
+        Some(self.address..self.address.checked_add(self.size)?)
+    }
+}
+
+/// Information parsed from the headers of the image.
+struct Headers {
+    sections: Vec<Section>,
+    /// Preferred address of the image.
+    base: usize,
+    pointer_size: usize,
+}
+
+/// Reads little or big endian integers from the headers.
+struct Reader<'a> {
+    data: &'a [u8],
+    big_endian: bool,
+}
+
+impl<'a> Reader<'a> {
+    fn read<const N: usize>(&self, offset: usize) -> Option<u64> {
+        let bytes = self.data.get(offset..offset.checked_add(N)?)?;
+        let mut value = [0; 8];
+        if self.big_endian {
+            value[8 - N..].copy_from_slice(bytes);
+            Some(u64::from_be_bytes(value))
+        } else {
+            value[..N].copy_from_slice(bytes);
+            Some(u64::from_le_bytes(value))
+        }
+    }
+
+    fn u16(&self, offset: usize) -> Option<usize> {
+        self.read::<2>(offset).map(|v| v as usize)
+    }
+
+    fn u32(&self, offset: usize) -> Option<usize> {
+        self.read::<4>(offset).map(|v| v as usize)
+    }
+
+    fn u64(&self, offset: usize) -> Option<usize> {
+        self.read::<8>(offset).map(|v| v as usize)
+    }
+
+    /// Reads null terminated string of at most `max` bytes.
+    fn str(&self, offset: usize, max: usize) -> Option<String> {
+        let bytes = self.data.get(offset..)?;
+        let bytes = &bytes[..bytes.len().min(max)];
+        let bytes = bytes.split(|b| *b == 0).next()?;
+        Some(String::from_utf8_lossy(bytes).into_owned())
+    }
+}
+
+/// Parses headers of PE image.
+fn pe_headers(r: &Reader) -> Option<Headers> {
+    let nt = r.u32(0x3C)?;
+    if r.data.get(nt..nt.checked_add(4)?)? != b"PE\0\0" {
+        return None;
+    }
+
+    let count = r.u16(nt + 6)?;
+    let optional = nt + 24;
+    let (image_base, pointer_size) = match r.u16(optional)? {
+        0x10B => (r.u32(optional + 28)?, 4),
+        0x20B => (r.u64(optional + 24)?, 8),
+        _ => return None,
+    };
+
+    let headers = optional + r.u16(nt + 20)?;
+    let sections = (0..count)
+        .map(|i| {
+            let header = headers.checked_add(i.checked_mul(40)?)?;
+            // Raw data is padded to the file alignment, virtual size is the actual one.
+            let (virtual_size, raw_size) = (r.u32(header + 8)?, r.u32(header + 16)?);
+            let section = Section {
+                name: r.str(header, 8)?,
+                address: image_base.checked_add(r.u32(header + 12)?)?,
+                size: match virtual_size {
+                    0 => raw_size,
+                    _ => virtual_size.min(raw_size),
+                },
+                offset: r.u32(header + 20)?,
+            };
+            section.file_range()?;
+            section.address_range()?;
+            Some(section)
+        })
+        .collect::<Option<_>>()?;
+
+    Some(Headers {
+        sections,
+        base: image_base,
+        pointer_size,
+    })
+}
+
+/// Parses headers of ELF image. Sections that aren't loaded into memory are skipped.
+fn elf_headers(data: &[u8]) -> Option<Headers> {
+    const SHT_NOBITS: usize = 8;
+    const SHF_ALLOC: usize = 2;
+
+    let r = Reader {
+        data,
+        big_endian: *data.get(5)? == 2,
+    };
+    let wide = *data.get(4)? == 2;
+
+    let (shoff, shentsize, shnum, shstrndx) = if wide {
+        (r.u64(0x28)?, r.u16(0x3A)?, r.u16(0x3C)?, r.u16(0x3E)?)
+    } else {
+        (r.u32(0x20)?, r.u16(0x2E)?, r.u16(0x30)?, r.u16(0x32)?)
+    };
+
+    // `(name, type, flags, address, offset, size)`
+    let header = |i: usize| {
+        let h = shoff.checked_add(i.checked_mul(shentsize)?)?;
+        let r = Reader {
+            data: data.get(h..)?,
+            big_endian: r.big_endian,
+        };
+        Some(if wide {
+            (
+                r.u32(0)?,
+                r.u32(4)?,
+                r.u64(8)?,
+                r.u64(0x10)?,
+                r.u64(0x18)?,
+                r.u64(0x20)?,
+            )
+        } else {
+            (
+                r.u32(0)?,
+                r.u32(4)?,
+                r.u32(8)?,
+                r.u32(0xC)?,
+                r.u32(0x10)?,
+                r.u32(0x14)?,
+            )
+        })
+    };
+
+    let names = match shnum {
+        0 => 0,
+        _ => header(shstrndx)?.4,
+    };
+    let mut sections = vec![];
+    for i in 0..shnum {
+        let (name, kind, flags, address, offset, size) = header(i)?;
+        if kind != SHT_NOBITS && flags & SHF_ALLOC != 0 {
+            let section = Section {
+                name: r.str(names.checked_add(name)?, usize::MAX)?,
+                offset,
+                size,
+                address,
+            };
+            section.file_range()?;
+            section.address_range()?;
+            sections.push(section);
+        }
+    }
+
+    Some(Headers {
+        // Sections are placed at the same offsets from the base as in the file.
+        base: sections
+            .iter()
+            .map(|s| s.address.saturating_sub(s.offset))
+            .min()
+            .unwrap_or(0),
+        sections,
+        pointer_size: if wide { 8 } else { 4 },
+    })
+}
+
+/// Parses headers of PE or ELF image.
+/// Returns no sections if data is not an executable image and `None` if its headers are malformed.
+fn parse_headers(data: &[u8]) -> Option<Headers> {
+    match data {
+        [0x7F, b'E', b'L', b'F', ..] => elf_headers(data),
+        [b'M', b'Z', ..] => pe_headers(&Reader {
+            data,
+            big_endian: false,
+        }),
+        _ => Some(Headers {
+            sections: vec![],
+            base: 0,
+            pointer_size: core::mem::size_of::<usize>(),
+        }),
+    }
+}
+
+/// File mapped into memory, e.g. a dumped module or an executable from disk.
+/// Searching the whole file yields offsets from its start,
+/// searching a section of PE or ELF image yields virtual addresses.
+/// ```no_run
+/// # use faithe::pattern::{FileImage, Pattern, PatternSearcher};
+/// let image = FileImage::open("Something.exe")?;
+/// let pat = Pattern::from_ida_style("48 8B 05 ? ? ? ? C3");
+///
+/// let offset = image.find_first(&pat)?;
+/// let address = image.section(".text").unwrap().find_first(&pat)?;
+/// # Ok::<(), faithe::FaitheError>(())
+/// ```
+pub struct FileImage {
+    map: Mmap,
+    headers: Headers,
+}
+
+impl FileImage {
+    /// Maps the file into memory and parses its section headers if it is PE or ELF image.
+    /// Fails with [`FaitheError::InvalidImage`] if the headers are malformed.
+    /// ```
+    /// # use faithe::{pattern::FileImage, FaitheError};
+    /// // 64-bit ELF with section headers at the end of the address space.
+    /// let mut elf = vec![0; 0x40];
+    /// elf[..6].copy_from_slice(b"\x7FELF\x02\x01");
+    /// elf[0x28..0x30].copy_from_slice(&u64::MAX.to_le_bytes());
+    /// (elf[0x3A], elf[0x3C], elf[0x3E]) = (1, 2, 1);
+    ///
+    /// let path = std::env::temp_dir().join("faithe-malformed.elf");
+    /// std::fs::write(&path, elf)?;
+    /// assert!(matches!(FileImage::open(&path), Err(FaitheError::InvalidImage)));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn open(path: impl AsRef<Path>) -> crate::Result<Self> {
+        let file = File::open(path)?;
+        let map = unsafe { Mmap::map(&file)? };
+        let headers = parse_headers(&map).ok_or(FaitheError::InvalidImage)?;
+
+        Ok(Self { map, headers })
+    }
+
+    /// Returns the contents of the file.
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.map
+    }
+
+    /// Returns the sections of the image that are loaded into memory.
+    /// Empty if file is not PE or ELF image.
+    #[inline]
+    pub fn sections(&self) -> &[Section] {
+        &self.headers.sections
+    }
+
+    /// Returns the address image prefers to be loaded at, `0` if file is not PE or ELF image.
+    #[inline]
+    pub fn image_base(&self) -> usize {
+        self.headers.base
+    }
+
+    /// Returns the size of pointers of the image's architecture.
+    #[inline]
+    pub fn pointer_size(&self) -> usize {
+        self.headers.pointer_size
+    }
+
+    /// Returns the section with the name.
+    pub fn section(&self, name: impl AsRef<str>) -> Option<ImageSection<'_>> {
+        self.sections()
+            .iter()
+            .find(|s| s.name == name.as_ref())
+            .map(|section| ImageSection {
+                data: section
+                    .file_range()
+                    .and_then(|range| self.map.get(range))
+                    .unwrap_or_default(),
+                section,
+            })
+    }
+
+    /// Converts the offset from the start of the file into virtual address.
+    /// Returns `None` if offset doesn't belong to any section.
+    pub fn offset_to_address(&self, offset: usize) -> Option<usize> {
+        self.sections()
+            .iter()
+            .find(|s| s.file_range().is_some_and(|r| r.contains(&offset)))
+            .and_then(|s| s.address.checked_add(offset - s.offset))
+    }
+
+    /// Converts virtual address into the offset from the start of the file.
+    /// Returns `None` if address doesn't belong to any section.
+    pub fn address_to_offset(&self, address: usize) -> Option<usize> {
+        self.sections()
+            .iter()
+            .find(|s| s.address_range().is_some_and(|r| r.contains(&address)))
+            .and_then(|s| s.offset.checked_add(address - s.address))
+    }
+}
+
+/// Reads the image as if it was loaded at its preferred base, virtual addresses are converted into offsets.
+/// Addresses are offsets from the start of the file if it is not PE or ELF image.
+impl MemoryReader for FileImage {
+    fn read_bytes(&self, address: usize, buf: &mut [u8]) -> crate::Result<usize> {
+        let offset = match self.sections() {
+            [] => address,
+            _ => self
+                .address_to_offset(address)
+                .ok_or(FaitheError::QueryFailed)?,
+        };
+        let data = self.data().get(offset..).unwrap_or_default();
+        let len = data.len().min(buf.len());
+        buf[..len].copy_from_slice(&data[..len]);
+        Ok(len)
+    }
+}
+
+/// Data of a single section of [`FileImage`].
+/// Created by [`FileImage::section`].
+#[derive(Debug, Clone, Copy)]
+pub struct ImageSection<'a> {
+    /// Header of the section.
+    pub section: &'a Section,
+    /// Data of the section.
+    pub data: &'a [u8],
+}
+
+/// Searches the whole file for any pattern, patterns set or rich pattern. Yields offsets from the start of the file.
+impl<N: Needle> PatternSearcher<N> for FileImage {
+    type Output = N::Output;
+    type Iter = std::vec::IntoIter<N::Output>;
+
+    fn find_all(&self, needle: N) -> crate::Result<Self::Iter> {
+        let mut found = vec![];
+        needle.find_in(self.data(), 0, &mut found);
+        Ok(found.into_iter())
+    }
+}
+
+/// Searches the section for any pattern, patterns set or rich pattern. Yields virtual addresses.
+impl<'a, N: Needle> PatternSearcher<N> for ImageSection<'a> {
+    type Output = N::Output;
+    type Iter = std::vec::IntoIter<N::Output>;
+
+    fn find_all(&self, needle: N) -> crate::Result<Self::Iter> {
+        let mut found = vec![];
+        needle.find_in(self.data, self.section.address, &mut found);
+        Ok(found.into_iter())
+    }
+}
@@ -1,99 +1,220 @@
 extern crate alloc;
-use alloc::vec::Vec;
+use alloc::{borrow::Cow, vec::Vec};
 
 mod searcher;
 pub use searcher::*;
 
+mod scan;
+pub use scan::FindIter;
+
+mod set;
+pub use set::*;
+
+mod chunked;
+pub use chunked::*;
+
+#[cfg(all(feature = "rayon", not(feature = "no-std")))]
+mod parallel;
+
+mod rich;
+pub use rich::*;
+
+#[cfg(all(feature = "memmap2", not(feature = "no-std")))]
+mod image;
+#[cfg(all(feature = "memmap2", not(feature = "no-std")))]
+pub use image::*;
+
+mod value;
+pub use value::*;
+
+mod fuzzy;
+pub use fuzzy::*;
+
+mod capture;
+pub use capture::*;
+
+mod format;
+
+mod analysis;
+pub use analysis::*;
+
+#[cfg(feature = "iced-x86")]
+mod signature;
+#[cfg(feature = "iced-x86")]
+pub use signature::*;
+
+mod parse;
+#[doc(hidden)]
+pub use parse::{__pattern_parse, __pattern_size};
+
 use crate::FaitheError;
 
-#[derive(Debug, Clone, Copy)]
-pub(crate) enum ByteMatch {
-    Exact(u8),
-    Any,
+/// Single byte of the pattern. Byte `b` matches if `b & mask == value`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ByteMatch {
+    value: u8,
+    mask: u8,
 }
 
 impl ByteMatch {
+    /// Matches any byte.
+    pub const ANY: Self = Self { value: 0, mask: 0 };
+
+    /// Matches only `value`.
+    #[inline]
+    pub const fn exact(value: u8) -> Self {
+        Self { value, mask: 0xFF }
+    }
+
+    /// Matches bytes whose bits selected by `mask` are equal to the same bits of `value`.
+    /// ```
+    /// # use faithe::pattern::ByteMatch;
+    /// let low = ByteMatch::masked(0x05, 0x0F);
+    /// assert!(low.matches(0x05) && low.matches(0xF5));
+    /// assert!(!low.matches(0x06));
+    /// ```
     #[inline]
-    pub fn matches(&self, b: u8) -> bool {
-        match self {
-            ByteMatch::Exact(e) => *e == b,
-            ByteMatch::Any => true,
+    pub const fn masked(value: u8, mask: u8) -> Self {
+        Self {
+            value: value & mask,
+            mask,
         }
     }
+
+    /// Value of the bits that are checked.
+    #[inline]
+    pub const fn value(&self) -> u8 {
+        self.value
+    }
+
+    /// Bits that are checked.
+    #[inline]
+    pub const fn mask(&self) -> u8 {
+        self.mask
+    }
+
+    /// Returns `true` if every bit of the byte is checked.
+    #[inline]
+    pub const fn is_exact(&self) -> bool {
+        self.mask == 0xFF
+    }
+
+    /// Returns `true` if byte matches anything.
+    #[inline]
+    pub const fn is_any(&self) -> bool {
+        self.mask == 0
+    }
+
+    /// Checks if `b` is matched by this byte.
+    #[inline]
+    pub const fn matches(&self, b: u8) -> bool {
+        b & self.mask == self.value
+    }
 }
 
 /// Memory pattern
-#[derive(Debug, Clone)]
-pub struct Pattern(pub(crate) Vec<ByteMatch>);
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Pattern {
+    pub(crate) bytes: Cow<'static, [ByteMatch]>,
+    pub(crate) captures: Cow<'static, [Capture]>,
+}
 
 impl Pattern {
     pub(crate) fn len(&self) -> usize {
-        self.0.len()
+        self.bytes.len()
     }
 
     pub(crate) fn matches(&self, data: &[u8]) -> bool {
-        data.iter().zip(self.0.iter()).all(|(b, m)| m.matches(*b))
+        data.iter().zip(self.bytes.iter()).all(|(b, m)| m.matches(*b))
+    }
+
+    fn parse(pat: &str, peid: bool) -> crate::Result<Self> {
+        if !pat.is_ascii() {
+            return Err(FaitheError::NonAsciiPattern);
+        }
+
+        let mut bytes = Vec::new();
+        let mut captures = Vec::new();
+        let mut parser = parse::Parser::new(pat, peid);
+        while let Some(piece) = parser.next().map_err(|_| FaitheError::InvalidPattern)? {
+            match piece {
+                parse::Piece::Byte(b) => bytes.push(b),
+                parse::Piece::Typed(capture) => {
+                    bytes.resize(bytes.len() + capture.len, ByteMatch::ANY);
+                    captures.push(capture);
+                }
+                parse::Piece::Group(capture) => captures.push(capture),
+            }
+        }
+
+        Ok(Self {
+            bytes: bytes.into(),
+            captures: captures.into(),
+        })
+    }
+
+    /// Creates pattern from static data without allocating. Used by [`crate::pattern!`].
+    /// Captures must lie inside of the pattern's bytes.
+    #[inline]
+    pub const fn from_static(bytes: &'static [ByteMatch], captures: &'static [Capture]) -> Self {
+        Self {
+            bytes: Cow::Borrowed(bytes),
+            captures: Cow::Borrowed(captures),
+        }
+    }
+}
+
+impl From<Vec<ByteMatch>> for Pattern {
+    fn from(bytes: Vec<ByteMatch>) -> Self {
+        Self {
+            bytes: bytes.into(),
+            captures: Cow::Borrowed(&[]),
+        }
+    }
+}
+
+impl FromIterator<ByteMatch> for Pattern {
+    fn from_iter<T: IntoIterator<Item = ByteMatch>>(iter: T) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
     }
 }
 
 impl Pattern {
     /// Parses ida style pattern.
+    /// Besides whole byte wildcards (`?`), nibble wildcards (`?5`, `E?`) and explicit masks (`AA&F0`) are supported.
+    ///
+    /// Bytes can be captured by enclosing them into `[` and `]` or with typed wildcards:
+    /// `$u8`, `$u16`, `$u32`, `$u64`, `$i8`, `$i16`, `$i32`, `$i64`, `$rel8` and `$rel32`.
+    /// See [`Pattern::match_at`].
     /// # Panics
     /// Panics if pattern of invalid style was supplied or failed to parse a byte.
     /// ```
     /// # use faithe::pattern::Pattern;
     /// let ida_pat = Pattern::from_ida_style("48 89 85 F0 00 00 00 4C 8B ? ? ? ? ? 48 8D");
+    /// let nibble_pat = Pattern::from_ida_style("48 8B 0? ?5 E8 C0&F0");
+    /// assert_eq!(nibble_pat.find_iter(&[0x48, 0x8B, 0x0D, 0x15, 0xE8, 0xC7]).next(), Some(0));
+    /// assert_eq!(nibble_pat.find_iter(&[0x48, 0x8B, 0x1D, 0x15, 0xE8, 0xC7]).next(), None);
     /// ```
     pub fn from_ida_style(pat: impl AsRef<str>) -> Self {
-        assert!(pat.as_ref().is_ascii());
-
-        Self(
-            pat.as_ref()
-                .split_ascii_whitespace()
-                .map(|s| {
-                    if s == "?" {
-                        ByteMatch::Any
-                    } else {
-                        ByteMatch::Exact(
-                            u8::from_str_radix(s, 16).expect("Failed to parse the pattern."),
-                        )
-                    }
-                })
-                .collect::<Vec<ByteMatch>>(),
+        crate::__expect!(
+            Self::try_from_ida_style(pat),
+            "Failed to parse the pattern."
         )
     }
 
     /// Parses ida style pattern. Same as [`Self::from_ida_style`] but no panics.
-    /// # Panics
-    /// Panics if pattern of invalid style was supplied or failed to parse a byte.
     /// ```
     /// # use faithe::pattern::Pattern;
-    /// let ida_pat = Pattern::from_ida_style("48 89 85 F0 00 00 00 4C 8B ? ? ? ? ? 48 8D");
+    /// assert!(Pattern::try_from_ida_style("48 89 85 F0 00 00 00 4C 8B ? ? ? ? ? 48 8D").is_ok());
+    /// assert!(Pattern::try_from_ida_style("48 8G").is_err());
     /// ```
     pub fn try_from_ida_style(pat: impl AsRef<str>) -> crate::Result<Self> {
-        if pat.as_ref().is_ascii() {
-            Err(FaitheError::NonAsciiPattern)
-        } else {
-            Ok(Self(
-                pat.as_ref()
-                    .split_ascii_whitespace()
-                    .map(|s| {
-                        if s == "?" {
-                            Ok(ByteMatch::Any)
-                        } else {
-                            if let Ok(b) = u8::from_str_radix(s, 16) {
-                                Ok(ByteMatch::Exact(b))
-                            } else {
-                                Err(FaitheError::InvalidPattern)
-                            }
-                        }
-                    })
-                    .collect::<crate::Result<Vec<ByteMatch>>>()?,
-            ))
-        }
+        Self::parse(pat.as_ref(), false)
     }
 
     /// Parses PEiD style pattern.
+    /// Besides whole byte wildcards (`??`), nibble wildcards (`?5`, `E?`) and explicit masks (`AA&F0`) are supported.
+    /// Captures are written the same way as in [`Self::from_ida_style`].
     /// # Panics
     /// Panics if pattern of invalid style was supplied or failed to parse a byte.
     /// ```
@@ -101,51 +222,20 @@ impl Pattern {
     /// let peid_pat = Pattern::from_peid_style("48 89 85 F0 00 00 00 4C 8B ?? ?? ?? ?? ?? 48 8D");
     /// ```
     pub fn from_peid_style(pat: impl AsRef<str>) -> Self {
-        Self(
-            pat.as_ref()
-                .split_ascii_whitespace()
-                .map(|s| {
-                    assert_eq!(s.len(), 2);
-                    if s == "??" {
-                        ByteMatch::Any
-                    } else {
-                        ByteMatch::Exact(
-                            u8::from_str_radix(s, 16).expect("Failed to parse the pattern."),
-                        )
-                    }
-                })
-                .collect::<Vec<ByteMatch>>(),
+        crate::__expect!(
+            Self::try_from_peid_style(pat),
+            "Failed to parse the pattern."
         )
     }
 
-    /// Parses PEiD style pattern.
-    /// # Panics
-    /// Panics if pattern of invalid style was supplied or failed to parse a byte.
+    /// Parses PEiD style pattern. Same as [`Self::from_peid_style`] but no panics.
     /// ```
     /// # use faithe::pattern::Pattern;
-    /// let peid_pat = Pattern::from_peid_style("48 89 85 F0 00 00 00 4C 8B ?? ?? ?? ?? ?? 48 8D");
+    /// assert!(Pattern::try_from_peid_style("48 89 85 F0 00 00 00 4C 8B ?? ?? ?? ?? ?? 48 8D").is_ok());
+    /// assert!(Pattern::try_from_peid_style("48 89 ?").is_err());
     /// ```
     pub fn try_from_peid_style(pat: impl AsRef<str>) -> crate::Result<Self> {
-        if pat.as_ref().is_ascii() {
-            Err(FaitheError::NonAsciiPattern)
-        } else {
-            Ok(Self(
-                pat.as_ref()
-                    .split_ascii_whitespace()
-                    .map(|s| {
-                        if s == "??" {
-                            Ok(ByteMatch::Any)
-                        } else {
-                            if let Ok(b) = u8::from_str_radix(s, 16) {
-                                Ok(ByteMatch::Exact(b))
-                            } else {
-                                Err(FaitheError::InvalidPattern)
-                            }
-                        }
-                    })
-                    .collect::<crate::Result<Vec<ByteMatch>>>()?,
-            ))
-        }
+        Self::parse(pat.as_ref(), true)
     }
 
     /// Parses code style pattern.
@@ -159,56 +249,89 @@ impl Pattern {
     /// );
     /// ```
     pub fn from_code_style(pat: &[u8], mask: &[u8]) -> Self {
-        assert_eq!(pat.len(), mask.len());
-
-        Self(
-            pat.iter()
-                .zip(mask.iter())
-                .map(|(p, m)| {
-                    if *m == b'?' {
-                        ByteMatch::Any
-                    } else {
-                        ByteMatch::Exact(*p)
-                    }
-                })
-                .collect(),
+        crate::__expect!(
+            Self::try_from_code_style(pat, mask),
+            "Length of mask is not equal to the length of the pattern."
         )
     }
 
-    /// Parses code style pattern.
-    /// # Panics
-    /// Panics if length os mask is not equal to the length of the pattern.
+    /// Parses code style pattern. Same as [`Self::from_code_style`] but no panics.
     /// ```
     /// # use faithe::pattern::Pattern;
-    /// let code_pat = Pattern::from_code_style(
+    /// let code_pat = Pattern::try_from_code_style(
     ///     b"\x48\x89\x85\xF0\x00\x00\x00\x4C\x8B\x00\x00\x00\x00\x00\x48\x8D",
     ///     b"xxxxxxxxx?????xx"
     /// );
+    /// assert!(code_pat.is_ok());
     /// ```
     pub fn try_from_code_style(pat: &[u8], mask: &[u8]) -> crate::Result<Self> {
         if pat.len() != mask.len() {
             Err(FaitheError::PatternMaskMismatch)
         } else {
-            Ok(Self(
-                pat.iter()
-                    .zip(mask.iter())
-                    .map(|(p, m)| {
-                        if *m == b'?' {
-                            ByteMatch::Any
-                        } else {
-                            ByteMatch::Exact(*p)
-                        }
-                    })
-                    .collect(),
-            ))
+            Ok(pat
+                .iter()
+                .zip(mask.iter())
+                .map(|(p, m)| {
+                    if *m == b'?' {
+                        ByteMatch::ANY
+                    } else {
+                        ByteMatch::exact(*p)
+                    }
+                })
+                .collect())
         }
     }
 }
 
 impl Pattern {
+    /// Returns an iterator over offsets of all pattern occurences in `data`.
+    /// Candidates are searched for using the fastest instruction set available at runtime (AVX2, SSE2 or scalar).
+    /// ```
+    /// # use faithe::pattern::Pattern;
+    /// let pat = Pattern::from_ida_style("E8 ? ? ? ? 90");
+    /// let data = [0x90, 0xE8, 1, 2, 3, 4, 0x90, 0xE8, 0, 0, 0, 0, 0x90];
+    /// assert_eq!(pat.find_iter(&data).collect::<Vec<_>>(), [1, 7]);
+    /// ```
+    /// Results are always the same as of checking every position one by one:
+    /// ```
+    /// # use faithe::pattern::{ByteMatch, Pattern};
+    /// let mut seed = 0x2545F4914F6CDD1Du64;
+    /// let mut rand = move || {
+    ///     seed ^= seed << 13;
+    ///     seed ^= seed >> 7;
+    ///     seed ^= seed << 17;
+    ///     seed
+    /// };
+    /// for _ in 0..300 {
+    ///     // Small alphabet so that matches actually happen.
+    ///     let data = (0..rand() % 512).map(|_| (rand() % 4) as u8 * 0x11).collect::<Vec<u8>>();
+    ///     let bytes = (0..1 + rand() % 6)
+    ///         .map(|_| match rand() % 6 {
+    ///             0 => ByteMatch::ANY,
+    ///             1 => ByteMatch::masked((rand() % 4) as u8, 0x0F),
+    ///             _ => ByteMatch::exact((rand() % 4) as u8 * 0x11),
+    ///         })
+    ///         .collect::<Vec<_>>();
+    ///     let naive = data
+    ///         .windows(bytes.len())
+    ///         .enumerate()
+    ///         .filter(|(_, w)| w.iter().zip(&bytes).all(|(b, m)| m.matches(*b)))
+    ///         .map(|(i, _)| i)
+    ///         .collect::<Vec<_>>();
+    ///     let pat = bytes.iter().copied().collect::<Pattern>();
+    ///     assert_eq!(naive, pat.find_iter(&data).collect::<Vec<_>>(), "{:?}", pat);
+    /// }
+    /// ```
+    #[inline]
+    pub fn find_iter<'p, 'd>(&'p self, data: &'d [u8]) -> FindIter<'p, 'd> {
+        FindIter::new(self, data)
+    }
+
     /// Finds all pattern occurences in memory range
-    /// Panics
+    /// # Panics
     /// if `from` > `to`
+    /// # Safety
+    /// Whole range from `from` to `to` must be readable.
     pub unsafe fn find_all(
         &self,
         from: *const u8,
@@ -216,10 +339,10 @@ impl Pattern {
     ) -> impl Iterator<Item = *const u8> + '_ {
         assert!(to as usize >= from as usize);
 
-        core::slice::from_raw_parts(from, to.offset_from(from) as usize)
-            .windows(self.len())
-            .enumerate()
-            .filter(|(_, w)| self.matches(*w))
-            .map(move |(i, _)| from.add(i))
+        self.find_iter(core::slice::from_raw_parts(
+            from,
+            to.offset_from(from) as usize,
+        ))
+        .map(move |i| from.add(i))
     }
 }
@@ -0,0 +1,149 @@
+use super::{ChunkScanner, Needle, Pattern};
+use crate::memory::MemoryReader;
+use core::{iter, ops::Range};
+use rayon::prelude::*;
+
+/// Size of the part of a slice that is scanned by a single task.
+const SLICE_CHUNK: usize = 0x40000;
+
+/// Splits `ranges` into parts of at most `chunk` bytes.
+/// Returns every part together with the end of the continuous range it belongs to.
+fn split(
+    ranges: impl IntoIterator<Item = Range<usize>>,
+    chunk: usize,
+) -> Vec<(Range<usize>, usize)> {
+    let mut merged: Vec<Range<usize>> = vec![];
+    for r in ranges {
+        match merged.last_mut() {
+            Some(last) if last.end == r.start => last.end = r.end,
+            _ => merged.push(r),
+        }
+    }
+
+    merged
+        .into_iter()
+        .flat_map(|r| {
+            r.clone()
+                .step_by(chunk)
+                .map(move |start| (start..(start + chunk).min(r.end), r.end))
+        })
+        .collect()
+}
+
+impl Pattern {
+    /// Returns offsets of all pattern occurences in `data` ordered by offset.
+    /// Parts of `data` are scanned simultaneously by all threads of the `rayon` pool.
+    /// ```
+    /// # use faithe::pattern::Pattern;
+    /// let mut data = vec![0; 0x100000];
+    /// data[0x3FFFF..0x40002].copy_from_slice(&[0xE8, 0x90, 0xC3]);
+    /// data[0xFFFFD..].copy_from_slice(&[0xE8, 0x90, 0xC3]);
+    /// let pat = Pattern::from_ida_style("E8 ? C3");
+    /// assert_eq!(pat.par_find_all(&data), pat.find_iter(&data).collect::<Vec<_>>());
+    /// assert_eq!(pat.par_find_first(&data), Some(0x3FFFF));
+    /// ```
+    pub fn par_find_all(&self, data: &[u8]) -> Vec<usize> {
+        split(iter::once(0..data.len()), SLICE_CHUNK)
+            .into_par_iter()
+            .flat_map_iter(|(part, _)| self.find_in_part(data, part))
+            .collect()
+    }
+
+    /// Returns offset of the first pattern occurence in `data`.
+    /// Parts of `data` are scanned simultaneously, parts after the first occurence are cancelled.
+    pub fn par_find_first(&self, data: &[u8]) -> Option<usize> {
+        split(iter::once(0..data.len()), SLICE_CHUNK)
+            .into_par_iter()
+            .find_map_first(|(part, _)| self.find_in_part(data, part).next())
+    }
+
+    /// Finds occurences that start in `part` of `data`.
+    fn find_in_part<'a>(
+        &'a self,
+        data: &'a [u8],
+        part: Range<usize>,
+    ) -> impl Iterator<Item = usize> + 'a {
+        let end = (part.end + self.len().saturating_sub(1)).min(data.len());
+        self.find_iter(&data[part.start..end])
+            .map(move |o| part.start + o)
+            .take_while(move |o| *o < part.end)
+    }
+}
+
+impl<R: MemoryReader + Sync> ChunkScanner<R> {
+    /// Returns all occurences of the needle in `ranges` of memory ordered by address.
+    /// Chunks are read and scanned simultaneously by all threads of the `rayon` pool.
+    /// ```
+    /// # use faithe::{memory::MemoryReader, pattern::{ChunkScanner, Pattern, PatternSet}};
+    /// struct Mem(Vec<u8>);
+    ///
+    /// impl MemoryReader for Mem {
+    ///     fn read_bytes(&self, address: usize, buf: &mut [u8]) -> Result<usize, faithe::FaitheError> {
+    ///         buf.copy_from_slice(&self.0[address..address + buf.len()]);
+    ///         Ok(buf.len())
+    ///     }
+    /// }
+    ///
+    /// let mut mem = vec![0; 0x10000];
+    /// mem[0x1FFF..0x2001].copy_from_slice(&[0xE8, 0xC3]);
+    /// mem[0x8000..0x8002].copy_from_slice(&[0xE8, 0xC3]);
+    /// let scanner = ChunkScanner::new(Mem(mem)).chunk_size(0x1000);
+    ///
+    /// let pat = Pattern::from_ida_style("E8 C3");
+    /// assert_eq!(scanner.par_find_all(&pat, [0..0x10000]), [0x1FFF, 0x8000]);
+    /// assert_eq!(scanner.par_find_first(&pat, [0x2000..0x10000]), Some(0x8000));
+    ///
+    /// let set = PatternSet::new([Pattern::from_ida_style("C3"), pat]);
+    /// assert_eq!(
+    ///     scanner.par_find_all(&set, [0..0x10000]),
+    ///     [(1, 0x1FFF), (0, 0x2000), (1, 0x8000), (0, 0x8001)]
+    /// );
+    /// ```
+    pub fn par_find_all<N>(
+        &self,
+        needle: N,
+        ranges: impl IntoIterator<Item = Range<usize>>,
+    ) -> Vec<N::Output>
+    where
+        N: Needle + Sync,
+        N::Output: Send,
+    {
+        split(ranges, self.chunk_size)
+            .into_par_iter()
+            .flat_map_iter(|(part, limit)| self.find_in_part(&needle, part, limit))
+            .collect()
+    }
+
+    /// Returns the first occurence of the needle in `ranges` of memory.
+    /// Chunks are read and scanned simultaneously, chunks after the first occurence are cancelled.
+    pub fn par_find_first<N>(
+        &self,
+        needle: N,
+        ranges: impl IntoIterator<Item = Range<usize>>,
+    ) -> Option<N::Output>
+    where
+        N: Needle + Sync,
+        N::Output: Send,
+    {
+        split(ranges, self.chunk_size)
+            .into_par_iter()
+            .find_map_first(|(part, limit)| self.find_in_part(&needle, part, limit).next())
+    }
+
+    /// Finds occurences that start in `part`, reading the bytes after it up to `limit`
+    /// if occurence crosses its end.
+    fn find_in_part<'a, N: Needle>(
+        &'a self,
+        needle: &'a N,
+        part: Range<usize>,
+        limit: usize,
+    ) -> impl Iterator<Item = N::Output> + 'a {
+        let end = (part.end + needle.max_len().saturating_sub(1)).min(limit);
+        ChunkScanner {
+            reader: &self.reader,
+            chunk_size: self.chunk_size,
+        }
+        .find_iter(needle, iter::once(part.start..end))
+        .take_while(move |o| N::address(o) < part.end)
+    }
+}
@@ -0,0 +1,223 @@
+//! Pattern parser that can run both at runtime and at compile time.
+use super::{ByteMatch, Capture, CaptureKind};
+
+/// Single element of the parsed pattern.
+pub(crate) enum Piece {
+    /// Single byte.
+    Byte(ByteMatch),
+    /// Typed capture, wildcard bytes of `len` are implied.
+    Typed(Capture),
+    /// Closed group of raw bytes. Bytes themselves were already returned.
+    Group(Capture),
+}
+
+/// Splits IDA/PEiD style pattern into pieces.
+pub(crate) struct Parser<'a> {
+    pat: &'a [u8],
+    pos: usize,
+    peid: bool,
+    /// Amount of bytes parsed so far.
+    len: usize,
+    /// Start of the currently open `[` group.
+    group: Option<usize>,
+}
+
+impl<'a> Parser<'a> {
+    pub(crate) const fn new(pat: &'a str, peid: bool) -> Self {
+        Self {
+            pat: pat.as_bytes(),
+            pos: 0,
+            peid,
+            len: 0,
+            group: None,
+        }
+    }
+
+    /// Returns the next piece of the pattern or `None` if whole pattern was parsed.
+    pub(crate) const fn next(&mut self) -> Result<Option<Piece>, &'static str> {
+        while self.pos < self.pat.len() && self.pat[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+        if self.pos == self.pat.len() {
+            return if self.group.is_some() {
+                Err("Unclosed capture group.")
+            } else {
+                Ok(None)
+            };
+        }
+
+        match self.pat[self.pos] {
+            b'[' => {
+                if self.group.is_some() {
+                    return Err("Capture groups can't be nested.");
+                }
+                self.group = Some(self.len);
+                self.pos += 1;
+                self.next()
+            }
+            b']' => match self.group {
+                Some(offset) if offset < self.len => {
+                    self.group = None;
+                    self.pos += 1;
+                    Ok(Some(Piece::Group(Capture {
+                        offset,
+                        len: self.len - offset,
+                        kind: CaptureKind::Bytes,
+                    })))
+                }
+                _ => Err("Unexpected `]`."),
+            },
+            _ => {
+                let start = self.pos;
+                while self.pos < self.pat.len()
+                    && !self.pat[self.pos].is_ascii_whitespace()
+                    && self.pat[self.pos] != b'['
+                    && self.pat[self.pos] != b']'
+                {
+                    self.pos += 1;
+                }
+                let token = self.pat.split_at(self.pos).0.split_at(start).1;
+
+                if let [b'$', name @ ..] = token {
+                    if self.group.is_some() {
+                        return Err("Typed captures can't be placed inside of a capture group.");
+                    }
+                    match parse_capture_kind(name) {
+                        Some((kind, len)) => {
+                            let capture = Capture {
+                                offset: self.len,
+                                len,
+                                kind,
+                            };
+                            self.len += len;
+                            Ok(Some(Piece::Typed(capture)))
+                        }
+                        None => Err("Unknown capture type."),
+                    }
+                } else {
+                    match parse_byte(token, self.peid) {
+                        Some(b) => {
+                            self.len += 1;
+                            Ok(Some(Piece::Byte(b)))
+                        }
+                        None => Err("Failed to parse the pattern."),
+                    }
+                }
+            }
+        }
+    }
+}
+
+const fn parse_nibble(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+const fn parse_hex(s: &[u8]) -> Option<u8> {
+    match s {
+        [lo] => parse_nibble(*lo),
+        [hi, lo] => match (parse_nibble(*hi), parse_nibble(*lo)) {
+            (Some(hi), Some(lo)) => Some(hi << 4 | lo),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Parses single byte token: `AB`, `?`/`??`, `?B`, `A?` or `AB&F0`.
+pub(super) const fn parse_byte(token: &[u8], peid: bool) -> Option<ByteMatch> {
+    let mut i = 0;
+    while i < token.len() {
+        if token[i] == b'&' {
+            let (value, mask) = token.split_at(i);
+            return match (parse_hex(value), parse_hex(mask.split_at(1).1)) {
+                (Some(value), Some(mask)) => Some(ByteMatch::masked(value, mask)),
+                _ => None,
+            };
+        }
+        i += 1;
+    }
+
+    match token {
+        [b'?'] if !peid => Some(ByteMatch::ANY),
+        [b'?', b'?'] => Some(ByteMatch::ANY),
+        [b'?', lo] => match parse_nibble(*lo) {
+            Some(lo) => Some(ByteMatch::masked(lo, 0x0F)),
+            None => None,
+        },
+        [hi, b'?'] => match parse_nibble(*hi) {
+            Some(hi) => Some(ByteMatch::masked(hi << 4, 0xF0)),
+            None => None,
+        },
+        _ => match parse_hex(token) {
+            Some(b) => Some(ByteMatch::exact(b)),
+            None => None,
+        },
+    }
+}
+
+/// Parses the name of typed capture (without `$`) into its kind and width.
+const fn parse_capture_kind(name: &[u8]) -> Option<(CaptureKind, usize)> {
+    Some(match name {
+        b"u8" => (CaptureKind::Unsigned, 1),
+        b"u16" => (CaptureKind::Unsigned, 2),
+        b"u32" => (CaptureKind::Unsigned, 4),
+        b"u64" => (CaptureKind::Unsigned, 8),
+        b"i8" => (CaptureKind::Signed, 1),
+        b"i16" => (CaptureKind::Signed, 2),
+        b"i32" => (CaptureKind::Signed, 4),
+        b"i64" => (CaptureKind::Signed, 8),
+        b"rel8" => (CaptureKind::Relative, 1),
+        b"rel32" => (CaptureKind::Relative, 4),
+        _ => return None,
+    })
+}
+
+/// Returns the amount of bytes and captures in IDA style pattern.
+/// # Panics
+/// If pattern is invalid, which results in a compile error when evaluated in constant context.
+#[doc(hidden)]
+pub const fn __pattern_size(pat: &str) -> (usize, usize) {
+    let mut parser = Parser::new(pat, false);
+    let mut captures = 0;
+    loop {
+        match parser.next() {
+            Ok(Some(Piece::Byte(_))) => {}
+            Ok(Some(Piece::Typed(_) | Piece::Group(_))) => captures += 1,
+            Ok(None) => break (parser.len, captures),
+            Err(e) => panic!("{}", e),
+        }
+    }
+}
+
+/// Parses IDA style pattern into arrays of bytes and captures.
+/// `B` and `C` must be equal to the values returned by [`__pattern_size`].
+#[doc(hidden)]
+pub const fn __pattern_parse<const B: usize, const C: usize>(
+    pat: &str,
+) -> ([ByteMatch; B], [Capture; C]) {
+    let mut bytes = [ByteMatch::ANY; B];
+    let mut captures = [Capture {
+        offset: 0,
+        len: 0,
+        kind: CaptureKind::Bytes,
+    }; C];
+
+    let mut parser = Parser::new(pat, false);
+    let mut c = 0;
+    loop {
+        match parser.next() {
+            Ok(Some(Piece::Byte(b))) => bytes[parser.len - 1] = b,
+            Ok(Some(Piece::Typed(capture) | Piece::Group(capture))) => {
+                captures[c] = capture;
+                c += 1;
+            }
+            Ok(None) => break (bytes, captures),
+            Err(e) => panic!("{}", e),
+        }
+    }
+}
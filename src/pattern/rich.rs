@@ -0,0 +1,426 @@
+extern crate alloc;
+use super::{
+    format::write_byte, parse::parse_byte, ByteMatch, FindIter, Needle, Pattern, PatternSearcher,
+};
+use crate::FaitheError;
+use alloc::{string::String, vec, vec::Vec};
+use core::{
+    fmt::{self, Write},
+    str::FromStr,
+};
+
+/// Single element of [`RichPattern`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Node {
+    /// Byte that has to match.
+    Byte(ByteMatch),
+    /// Byte that must not match.
+    Not(ByteMatch),
+    /// From `min` to `max` arbitrary bytes.
+    Jump(usize, usize),
+    /// Any of the sequences, earlier ones are tried first.
+    Alt(Vec<Vec<Node>>),
+}
+
+/// Returns the minimal and maximal length of the sequence.
+fn lengths(nodes: &[Node]) -> (usize, usize) {
+    nodes.iter().fold((0, 0), |(min, max), node| {
+        let (lo, hi) = match node {
+            Node::Byte(_) | Node::Not(_) => (1, 1),
+            Node::Jump(lo, hi) => (*lo, *hi),
+            Node::Alt(alts) => alts
+                .iter()
+                .map(|alt| lengths(alt))
+                .fold((usize::MAX, 0), |(min, max), (lo, hi)| {
+                    (min.min(lo), max.max(hi))
+                }),
+        };
+        (min + lo, max + hi)
+    })
+}
+
+/// Matches `nodes` against `data` at `pos`, then calls `k` with the end of the matched bytes.
+/// Every way to match the sequence is tried until `k` accepts one, shorter jumps and earlier alternatives first.
+fn run(
+    nodes: &[Node],
+    data: &[u8],
+    mut pos: usize,
+    k: &mut dyn FnMut(usize) -> Option<usize>,
+) -> Option<usize> {
+    for (i, node) in nodes.iter().enumerate() {
+        let rest = &nodes[i + 1..];
+        match node {
+            Node::Byte(m) | Node::Not(m) => match data.get(pos) {
+                Some(b) if m.matches(*b) == matches!(node, Node::Byte(_)) => pos += 1,
+                _ => return None,
+            },
+            Node::Jump(min, max) => {
+                return (*min..=*max)
+                    .take_while(|n| pos + n <= data.len())
+                    .find_map(|n| run(rest, data, pos + n, k));
+            }
+            Node::Alt(alts) => {
+                return alts
+                    .iter()
+                    .find_map(|alt| run(alt, data, pos, &mut |end| run(rest, data, end, k)));
+            }
+        }
+    }
+    k(pos)
+}
+
+/// Parser of YARA hex strings.
+struct Parser<'a> {
+    pat: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&mut self) -> Option<u8> {
+        while self.pat.get(self.pos).is_some_and(u8::is_ascii_whitespace) {
+            self.pos += 1;
+        }
+        self.pat.get(self.pos).copied()
+    }
+
+    /// Parses nodes up to the end of the pattern or the end of the current alternative.
+    fn sequence(&mut self) -> Option<Vec<Node>> {
+        let mut nodes = Vec::new();
+        while let Some(c) = self.peek() {
+            let node = match c {
+                b'|' | b')' => break,
+                b'(' => {
+                    self.pos += 1;
+                    self.alternation()?
+                }
+                b'[' => {
+                    self.pos += 1;
+                    self.jump()?
+                }
+                b'~' => {
+                    self.pos += 1;
+                    self.peek()?;
+                    Node::Not(self.byte().filter(|m| !m.is_any())?)
+                }
+                _ => Node::Byte(self.byte()?),
+            };
+            nodes.push(node);
+        }
+
+        (!nodes.is_empty()).then_some(nodes)
+    }
+
+    /// Parses alternatives after `(` up to and including `)`.
+    fn alternation(&mut self) -> Option<Node> {
+        let mut alts = vec![self.sequence()?];
+        loop {
+            self.pos += 1;
+            match self.pat.get(self.pos - 1)? {
+                b'|' => alts.push(self.sequence()?),
+                b')' => break Some(Node::Alt(alts)),
+                _ => break None,
+            }
+        }
+    }
+
+    /// Parses `n]` or `n-m]` after `[`.
+    fn jump(&mut self) -> Option<Node> {
+        let min = self.number()?;
+        let max = if self.peek()? == b'-' {
+            self.pos += 1;
+            self.number()?
+        } else {
+            min
+        };
+
+        (self.peek()? == b']' && min <= max).then(|| {
+            self.pos += 1;
+            Node::Jump(min, max)
+        })
+    }
+
+    fn number(&mut self) -> Option<usize> {
+        self.peek()?;
+        let start = self.pos;
+        while self.pat.get(self.pos).is_some_and(u8::is_ascii_digit) {
+            self.pos += 1;
+        }
+        core::str::from_utf8(&self.pat[start..self.pos])
+            .ok()?
+            .parse()
+            .ok()
+    }
+
+    fn byte(&mut self) -> Option<ByteMatch> {
+        let start = self.pos;
+        while self
+            .pat
+            .get(self.pos)
+            .is_some_and(|c| c.is_ascii_alphanumeric() || matches!(c, b'?' | b'&'))
+        {
+            self.pos += 1;
+        }
+        parse_byte(&self.pat[start..self.pos], true)
+    }
+}
+
+/// Pattern written in a subset of YARA hex strings.
+/// Besides the bytes [`Pattern`] supports it can contain:
+/// * bounded jumps over arbitrary bytes: `[4]` or `[2-6]`;
+/// * alternatives: `(74 | 75 | 0F 84)`, which can be nested;
+/// * negated bytes: `~00` matches anything but `00`, `~?0` matches bytes with low nibble other than `0`.
+///
+/// Every position where the pattern can match is reported, so occurences can overlap.
+/// ```
+/// # use faithe::pattern::RichPattern;
+/// let pat = RichPattern::from_yara_style("{ 48 85 C0 (74 | 75 | 0F (84 | 85) ?? ?? ??) ?? E8 [0-4] C3 }");
+/// let data = [0x48, 0x85, 0xC0, 0x0F, 0x85, 1, 2, 3, 4, 0xE8, 0x90, 0x90, 0xC3];
+/// assert_eq!(pat.find_iter(&data).collect::<Vec<_>>(), [0]);
+/// assert_eq!(pat.match_len(&data), Some(13));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RichPattern {
+    nodes: Vec<Node>,
+    /// Leading plain bytes, candidates are searched for with them.
+    prefix: Pattern,
+    min_len: usize,
+    max_len: usize,
+}
+
+impl RichPattern {
+    fn new(nodes: Vec<Node>) -> Self {
+        let (min_len, max_len) = lengths(&nodes);
+        let prefix = nodes
+            .iter()
+            .map_while(|node| match node {
+                Node::Byte(m) => Some(*m),
+                _ => None,
+            })
+            .collect();
+
+        Self {
+            nodes,
+            prefix,
+            min_len,
+            max_len,
+        }
+    }
+
+    /// Parses YARA style hex string. Surrounding `{` and `}` are optional, bytes must be separated.
+    /// Besides the bytes of PEiD style patterns, jumps, alternatives and negated bytes are supported.
+    /// # Panics
+    /// Panics if failed to parse the pattern.
+    pub fn from_yara_style(pat: impl AsRef<str>) -> Self {
+        crate::__expect!(
+            Self::try_from_yara_style(pat),
+            "Failed to parse the pattern."
+        )
+    }
+
+    /// Parses YARA style hex string. Same as [`Self::from_yara_style`] but no panics.
+    /// Jumps can't be the first or the last element of the pattern and must be bounded.
+    /// ```
+    /// # use faithe::pattern::RichPattern;
+    /// assert!(RichPattern::try_from_yara_style("E8 [4] (C3 | CC)").is_ok());
+    /// assert!(RichPattern::try_from_yara_style("[2] E8").is_err());
+    /// assert!(RichPattern::try_from_yara_style("E8 [6-2] C3").is_err());
+    /// assert!(RichPattern::try_from_yara_style("E8 [2-] C3").is_err());
+    /// assert!(RichPattern::try_from_yara_style("E8 (C3 | ) 90").is_err());
+    /// ```
+    pub fn try_from_yara_style(pat: impl AsRef<str>) -> crate::Result<Self> {
+        let pat = pat.as_ref();
+        if !pat.is_ascii() {
+            return Err(FaitheError::NonAsciiPattern);
+        }
+
+        let mut pat = pat.trim();
+        if let Some(inner) = pat.strip_prefix('{') {
+            pat = inner.strip_suffix('}').ok_or(FaitheError::InvalidPattern)?;
+        }
+
+        let mut parser = Parser {
+            pat: pat.as_bytes(),
+            pos: 0,
+        };
+        match parser.sequence() {
+            Some(nodes)
+                if parser.peek().is_none()
+                    && !matches!(nodes.first(), Some(Node::Jump(..)))
+                    && !matches!(nodes.last(), Some(Node::Jump(..))) =>
+            {
+                Ok(Self::new(nodes))
+            }
+            _ => Err(FaitheError::InvalidPattern),
+        }
+    }
+
+    /// Length of the shortest possible occurence.
+    #[inline]
+    pub fn min_len(&self) -> usize {
+        self.min_len
+    }
+
+    /// Length of the longest possible occurence.
+    #[inline]
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    /// Checks if pattern matches at the start of `data` and returns the length of the occurence.
+    /// Shorter jumps and earlier alternatives are preferred.
+    /// ```
+    /// # use faithe::pattern::RichPattern;
+    /// let pat = RichPattern::from_yara_style("AA (BB | BB CC) [0-2] DD");
+    /// assert_eq!(pat.match_len(&[0xAA, 0xBB, 0xDD]), Some(3));
+    /// assert_eq!(pat.match_len(&[0xAA, 0xBB, 0xCC, 0xDD]), Some(4));
+    /// assert_eq!(pat.match_len(&[0xAA, 0xBB, 0xCC, 0, 0, 0, 0xDD]), None);
+    /// ```
+    pub fn match_len(&self, data: &[u8]) -> Option<usize> {
+        run(&self.nodes, data, 0, &mut Some)
+    }
+
+    /// Returns an iterator over offsets of all pattern occurences in `data`.
+    /// ```
+    /// # use faithe::pattern::RichPattern;
+    /// // Occurences overlap and share their bytes.
+    /// let pat = RichPattern::from_yara_style("AA [0-2] AA");
+    /// assert_eq!(pat.find_iter(&[0xAA; 4]).collect::<Vec<_>>(), [0, 1, 2]);
+    ///
+    /// // Short alternative is not enough to match, so the longer one is tried.
+    /// let pat = RichPattern::from_yara_style("(AA | AA BB) CC");
+    /// assert_eq!(pat.find_iter(&[0xAA, 0xBB, 0xCC]).collect::<Vec<_>>(), [0]);
+    ///
+    /// // Negated bytes never match past the end of data.
+    /// let pat = RichPattern::from_yara_style("~00 ~00");
+    /// assert_eq!(pat.find_iter(&[1, 0, 2, 3]).collect::<Vec<_>>(), [2]);
+    /// ```
+    #[inline]
+    pub fn find_iter<'p, 'd>(&'p self, data: &'d [u8]) -> RichFindIter<'p, 'd> {
+        RichFindIter {
+            pat: self,
+            data,
+            anchored: (self.prefix.len() != 0).then(|| self.prefix.find_iter(data)),
+            pos: 0,
+        }
+    }
+}
+
+impl From<Pattern> for RichPattern {
+    /// Converts plain pattern, its captures are dropped.
+    fn from(pat: Pattern) -> Self {
+        Self::new(pat.bytes.iter().copied().map(Node::Byte).collect())
+    }
+}
+
+fn write_nodes(out: &mut String, nodes: &[Node]) {
+    for node in nodes {
+        if !out.is_empty() && !out.ends_with('(') {
+            out.push(' ');
+        }
+        match node {
+            Node::Byte(m) => write_byte(out, *m, "??"),
+            Node::Not(m) => {
+                out.push('~');
+                write_byte(out, *m, "??");
+            }
+            // Writing into a `String` never fails.
+            Node::Jump(min, max) if min == max => drop(write!(out, "[{}]", min)),
+            Node::Jump(min, max) => drop(write!(out, "[{}-{}]", min, max)),
+            Node::Alt(alts) => {
+                out.push('(');
+                for (i, alt) in alts.iter().enumerate() {
+                    if i != 0 {
+                        out.push_str(" |");
+                    }
+                    write_nodes(out, alt);
+                }
+                out.push(')');
+            }
+        }
+    }
+}
+
+/// Writes the pattern in YARA style without surrounding braces.
+/// ```
+/// # use faithe::pattern::RichPattern;
+/// let pat = RichPattern::from_yara_style("{ E8 ?? [2-4] ( 74|~75 ) }");
+/// assert_eq!(pat.to_string(), "E8 ?? [2-4] (74 | ~75)");
+/// assert_eq!(pat.to_string().parse::<RichPattern>().unwrap(), pat);
+/// ```
+impl fmt::Display for RichPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = String::new();
+        write_nodes(&mut out, &self.nodes);
+        f.write_str(&out)
+    }
+}
+
+impl FromStr for RichPattern {
+    type Err = FaitheError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from_yara_style(s)
+    }
+}
+
+/// Iterator over occurences of [`RichPattern`] in a byte slice.
+/// Created by [`RichPattern::find_iter`].
+pub struct RichFindIter<'p, 'd> {
+    pat: &'p RichPattern,
+    data: &'d [u8],
+    /// Candidates with matched prefix, `None` if pattern doesn't start with plain bytes.
+    anchored: Option<FindIter<'p, 'd>>,
+    /// Next position to check if there is no prefix.
+    pos: usize,
+}
+
+impl<'p, 'd> Iterator for RichFindIter<'p, 'd> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pat = self.pat;
+        let skip = pat.prefix.len();
+        loop {
+            let start = match &mut self.anchored {
+                Some(candidates) => candidates.next()?,
+                None if self.pos + pat.min_len <= self.data.len() => {
+                    self.pos += 1;
+                    self.pos - 1
+                }
+                None => return None,
+            };
+
+            if run(&pat.nodes[skip..], self.data, start + skip, &mut Some).is_some() {
+                return Some(start);
+            }
+        }
+    }
+}
+
+impl Needle for RichPattern {
+    type Output = usize;
+
+    #[inline]
+    fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    fn find_in(&self, data: &[u8], address: usize, out: &mut Vec<usize>) {
+        out.extend(self.find_iter(data).map(|o| address + o));
+    }
+
+    #[inline]
+    fn address(out: &usize) -> usize {
+        *out
+    }
+}
+
+impl<'p, 'd> PatternSearcher<&'p RichPattern> for &'d [u8] {
+    type Output = usize;
+    type Iter = RichFindIter<'p, 'd>;
+
+    fn find_all(&self, pat: &'p RichPattern) -> crate::Result<Self::Iter> {
+        Ok(pat.find_iter(self))
+    }
+}
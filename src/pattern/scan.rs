@@ -0,0 +1,311 @@
+use super::{ByteMatch, Pattern};
+
+/// Bytes sorted by how often they appear in x86 machine code, most common first.
+/// Bytes not listed here are considered rare.
+const COMMON_BYTES: [u8; 48] = [
+    0x00, 0xFF, 0xCC, 0x48, 0x8B, 0x89, 0x24, 0x0F, 0xE8, 0x4C, 0x44, 0x8D, 0x83, 0x01, 0x85, 0x74,
+    0xC0, 0x45, 0x10, 0x08, 0x20, 0x49, 0x4D, 0x75, 0x04, 0x40, 0xC3, 0x90, 0x41, 0x5C, 0x33, 0x18,
+    0x02, 0xEB, 0x30, 0x28, 0xC7, 0x84, 0x80, 0x38, 0x50, 0x0D, 0x05, 0x03, 0xE9, 0x8E, 0x54, 0x4E,
+];
+
+const fn byte_ranks() -> [u8; 256] {
+    let mut ranks = [0; 256];
+    let mut i = 0;
+    while i < COMMON_BYTES.len() {
+        ranks[COMMON_BYTES[i] as usize] = (COMMON_BYTES.len() - i) as u8;
+        i += 1;
+    }
+    ranks
+}
+
+/// How common each byte is, `0` being the rarest.
+pub(super) static BYTE_RANKS: [u8; 256] = byte_ranks();
+
+/// Two bytes of the pattern that are checked before verifying the whole pattern.
+#[derive(Debug, Clone, Copy)]
+struct Anchor {
+    first: usize,
+    first_byte: ByteMatch,
+    second: usize,
+    second_byte: ByteMatch,
+}
+
+impl Anchor {
+    /// Picks the rarest byte of the pattern and the rarest one from the rest of them.
+    /// Exact bytes are preferred over masked ones, wildcards are never picked.
+    fn pick(pat: &[ByteMatch]) -> Option<Self> {
+        let checked = || pat.iter().copied().enumerate().filter(|(_, m)| !m.is_any());
+        let score = |m: &ByteMatch| (m.mask().count_zeros(), BYTE_RANKS[m.value() as usize]);
+
+        let (first, first_byte) = checked().min_by_key(|(_, m)| score(m))?;
+        let (second, second_byte) = checked()
+            .filter(|(i, _)| *i != first)
+            .min_by_key(|(i, m)| (score(m), usize::MAX - i.abs_diff(first)))
+            .unwrap_or((first, first_byte));
+
+        Some(Self {
+            first,
+            first_byte,
+            second,
+            second_byte,
+        })
+    }
+
+    #[inline]
+    fn reach(&self) -> usize {
+        self.first.max(self.second)
+    }
+}
+
+/// Function that checks `width` consecutive candidates starting at `start` and returns a bitmask of those
+/// that have both anchor bytes matched.
+/// # Safety
+/// `start + anchor.reach() + width` must not exceed `data.len()`.
+type BlockFn = unsafe fn(&[u8], usize, &Anchor) -> u32;
+
+/// Candidate search strategy chosen at runtime.
+#[derive(Clone, Copy)]
+enum Engine {
+    Scalar,
+    #[cfg_attr(
+        not(any(target_arch = "x86", target_arch = "x86_64")),
+        allow(dead_code)
+    )]
+    Vector(usize, BlockFn),
+}
+
+impl Engine {
+    #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        not(feature = "no-std")
+    ))]
+    fn detect() -> Self {
+        if std::is_x86_feature_detected!("avx2") {
+            Self::Vector(32, x86::avx2)
+        } else if std::is_x86_feature_detected!("sse2") {
+            Self::Vector(16, x86::sse2)
+        } else {
+            Self::Scalar
+        }
+    }
+
+    #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "no-std"))]
+    fn detect() -> Self {
+        if cfg!(target_feature = "avx2") {
+            Self::Vector(32, x86::avx2)
+        } else if cfg!(target_feature = "sse2") {
+            Self::Vector(16, x86::sse2)
+        } else {
+            Self::Scalar
+        }
+    }
+
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+    fn detect() -> Self {
+        Self::Scalar
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod x86 {
+    use super::Anchor;
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn sse2(data: &[u8], start: usize, anchor: &Anchor) -> u32 {
+        let ptr = data.as_ptr().add(start);
+        let first = _mm_loadu_si128(ptr.add(anchor.first) as *const __m128i);
+        let second = _mm_loadu_si128(ptr.add(anchor.second) as *const __m128i);
+        let eq = _mm_and_si128(
+            _mm_cmpeq_epi8(
+                _mm_and_si128(first, _mm_set1_epi8(anchor.first_byte.mask() as i8)),
+                _mm_set1_epi8(anchor.first_byte.value() as i8),
+            ),
+            _mm_cmpeq_epi8(
+                _mm_and_si128(second, _mm_set1_epi8(anchor.second_byte.mask() as i8)),
+                _mm_set1_epi8(anchor.second_byte.value() as i8),
+            ),
+        );
+        _mm_movemask_epi8(eq) as u32
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn avx2(data: &[u8], start: usize, anchor: &Anchor) -> u32 {
+        let ptr = data.as_ptr().add(start);
+        let first = _mm256_loadu_si256(ptr.add(anchor.first) as *const __m256i);
+        let second = _mm256_loadu_si256(ptr.add(anchor.second) as *const __m256i);
+        let eq = _mm256_and_si256(
+            _mm256_cmpeq_epi8(
+                _mm256_and_si256(first, _mm256_set1_epi8(anchor.first_byte.mask() as i8)),
+                _mm256_set1_epi8(anchor.first_byte.value() as i8),
+            ),
+            _mm256_cmpeq_epi8(
+                _mm256_and_si256(second, _mm256_set1_epi8(anchor.second_byte.mask() as i8)),
+                _mm256_set1_epi8(anchor.second_byte.value() as i8),
+            ),
+        );
+        _mm256_movemask_epi8(eq) as u32
+    }
+}
+
+/// Iterator over offsets of all pattern occurences in a byte slice.
+/// Created by [`Pattern::find_iter`].
+pub struct FindIter<'p, 'd> {
+    pat: &'p Pattern,
+    data: &'d [u8],
+    anchor: Option<Anchor>,
+    engine: Engine,
+    /// Next candidate that wasn't checked yet.
+    pos: usize,
+    /// Start of the block `mask` was computed for.
+    block: usize,
+    mask: u32,
+}
+
+impl<'p, 'd> FindIter<'p, 'd> {
+    pub(crate) fn new(pat: &'p Pattern, data: &'d [u8]) -> Self {
+        Self {
+            anchor: Anchor::pick(&pat.bytes),
+            engine: Engine::detect(),
+            pos: 0,
+            block: 0,
+            mask: 0,
+            pat,
+            data,
+        }
+    }
+
+    /// Offset of the last position the pattern can start at.
+    #[inline]
+    fn last_start(&self) -> Option<usize> {
+        if self.pat.len() == 0 {
+            None
+        } else {
+            self.data.len().checked_sub(self.pat.len())
+        }
+    }
+
+    #[inline]
+    fn verify(&self, start: usize) -> bool {
+        self.data
+            .get(start..start + self.pat.len())
+            .is_some_and(|w| self.pat.matches(w))
+    }
+}
+
+impl<'p, 'd> Iterator for FindIter<'p, 'd> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let last = self.last_start()?;
+        let anchor = match self.anchor {
+            Some(anchor) => anchor,
+            None => {
+                // Pattern consists only of wildcards, so every position is a match.
+                let pos = self.pos;
+                self.pos += 1;
+                return (pos <= last).then_some(pos);
+            }
+        };
+
+        loop {
+            while self.mask != 0 {
+                let start = self.block + self.mask.trailing_zeros() as usize;
+                self.mask &= self.mask - 1;
+                if self.verify(start) {
+                    return Some(start);
+                }
+            }
+
+            if let Engine::Vector(width, block) = self.engine {
+                if self.pos + anchor.reach() + width <= self.data.len() {
+                    // SAFETY: bounds are checked right above.
+                    self.mask = unsafe { block(self.data, self.pos, &anchor) };
+                    self.block = self.pos;
+                    self.pos += width;
+                    continue;
+                }
+            }
+            break;
+        }
+
+        // Scalar path, also handles the tail that is too short for vector loads.
+        while self.pos <= last {
+            let from = self.pos + anchor.first;
+            let start = self.data[from..=last + anchor.first]
+                .iter()
+                .position(|b| anchor.first_byte.matches(*b))?
+                + self.pos;
+            self.pos = start + 1;
+
+            if anchor.second_byte.matches(self.data[start + anchor.second]) && self.verify(start) {
+                return Some(start);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(all(test, not(feature = "no-std")))]
+mod tests {
+    use super::{ByteMatch, Engine, FindIter, Pattern};
+
+    /// Engines that can run on this machine.
+    fn engines() -> Vec<Engine> {
+        #[allow(unused_mut)]
+        let mut engines = vec![Engine::Scalar];
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if std::is_x86_feature_detected!("sse2") {
+                engines.push(Engine::Vector(16, super::x86::sse2));
+            }
+            if std::is_x86_feature_detected!("avx2") {
+                engines.push(Engine::Vector(32, super::x86::avx2));
+            }
+        }
+        engines
+    }
+
+    #[test]
+    fn every_engine_matches_naive_search() {
+        let mut seed = 0x2545F4914F6CDD1Du64;
+        let mut rand = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+
+        for _ in 0..1000 {
+            // Small alphabet so that matches actually happen.
+            let data = (0..rand() % 512)
+                .map(|_| (rand() % 4) as u8 * 0x11)
+                .collect::<Vec<u8>>();
+            let bytes = (0..1 + rand() % 6)
+                .map(|_| match rand() % 6 {
+                    0 => ByteMatch::ANY,
+                    1 => ByteMatch::masked((rand() % 4) as u8, 0x0F),
+                    _ => ByteMatch::exact((rand() % 4) as u8 * 0x11),
+                })
+                .collect::<Vec<_>>();
+            let naive = data
+                .windows(bytes.len())
+                .enumerate()
+                .filter(|(_, w)| w.iter().zip(&bytes).all(|(b, m)| m.matches(*b)))
+                .map(|(i, _)| i)
+                .collect::<Vec<_>>();
+            let pat = bytes.iter().copied().collect::<Pattern>();
+
+            for engine in engines() {
+                let found = FindIter {
+                    engine,
+                    ..FindIter::new(&pat, &data)
+                };
+                assert_eq!(naive, found.collect::<Vec<_>>(), "{:?}", pat);
+            }
+        }
+    }
+}
@@ -1,17 +1,76 @@
-use super::Pattern;
+extern crate alloc;
+use super::{FindIter, Needle, Pattern};
+use alloc::{vec, vec::Vec};
 
 /// Trait implemented for types that can do pattern search.
-pub trait PatternSearcher {
+/// `P` is the kind of pattern that is searched for, e.g. a single [`Pattern`] or a [`super::PatternSet`].
+pub trait PatternSearcher<P = Pattern> {
     /// Pattern search output.
     type Output;
     /// Iterator over all occurences.
     type Iter: Iterator<Item = Self::Output>;
 
     /// Finds an iterator over all occurences of the pattern.
-    fn find_all(&self, pat: Pattern) -> crate::Result<Self::Iter>;
+    fn find_all(&self, pat: P) -> crate::Result<Self::Iter>;
 
     /// Returns first occurence of the pattern if present.
-    fn find_first(&self, pat: Pattern) -> crate::Result<Option<Self::Output>> {
+    fn find_first(&self, pat: P) -> crate::Result<Option<Self::Output>> {
         Ok(self.find_all(pat)?.next())
     }
 }
+
+impl PatternSearcher for &[u8] {
+    type Output = usize;
+    type Iter = vec::IntoIter<usize>;
+
+    /// Yields offsets of all occurences from the start of the slice.
+    fn find_all(&self, pat: Pattern) -> crate::Result<Self::Iter> {
+        Ok(pat.find_iter(self).collect::<Vec<_>>().into_iter())
+    }
+
+    fn find_first(&self, pat: Pattern) -> crate::Result<Option<usize>> {
+        Ok(pat.find_iter(self).next())
+    }
+}
+
+impl<'p, 'd> PatternSearcher<&'p Pattern> for &'d [u8] {
+    type Output = usize;
+    type Iter = FindIter<'p, 'd>;
+
+    /// Yields offsets of all occurences from the start of the slice.
+    /// ```
+    /// # use faithe::pattern::{Pattern, PatternSearcher};
+    /// let data: &[u8] = &[0x90, 0xE8, 0xC3, 0xE8, 0xC3];
+    /// let pat = Pattern::from_ida_style("E8 C3");
+    /// assert_eq!(data.find_all(&pat)?.collect::<Vec<_>>(), [1, 3]);
+    /// assert_eq!(data.find_first(pat)?, Some(1));
+    /// # Ok::<(), faithe::FaitheError>(())
+    /// ```
+    fn find_all(&self, pat: &'p Pattern) -> crate::Result<Self::Iter> {
+        Ok(pat.find_iter(self))
+    }
+}
+
+/// Searches the buffer for any pattern, patterns set or rich pattern. Yields offsets from the start of the buffer.
+/// ```
+/// # use faithe::pattern::{Pattern, PatternSearcher, PatternSet, RichPattern};
+/// let data = vec![0x90, 0xE8, 0xC3, 0x74, 0xC3];
+/// assert_eq!(data.find_first(Pattern::from_ida_style("E8 C3"))?, Some(1));
+///
+/// let set = PatternSet::new([Pattern::from_ida_style("C3"), Pattern::from_ida_style("90")]);
+/// assert_eq!(data.find_all(&set)?.collect::<Vec<_>>(), [(1, 0), (0, 2), (0, 4)]);
+///
+/// let rich = RichPattern::from_yara_style("(E8 | 74) C3");
+/// assert_eq!(data.find_all(&rich)?.collect::<Vec<_>>(), [1, 3]);
+/// # Ok::<(), faithe::FaitheError>(())
+/// ```
+impl<N: Needle> PatternSearcher<N> for Vec<u8> {
+    type Output = N::Output;
+    type Iter = vec::IntoIter<N::Output>;
+
+    fn find_all(&self, needle: N) -> crate::Result<Self::Iter> {
+        let mut found = vec![];
+        needle.find_in(self, 0, &mut found);
+        Ok(found.into_iter())
+    }
+}
@@ -0,0 +1,218 @@
+extern crate alloc;
+use super::{Pattern, PatternSearcher};
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
+use core::cmp::Reverse;
+
+/// Buckets of patterns indexed by anchor key, stored as one flat list.
+struct Buckets {
+    /// `entries[starts[key]..starts[key + 1]]` are patterns anchored with `key`.
+    starts: Vec<u32>,
+    /// Index of the pattern and offset of its anchor.
+    entries: Vec<(usize, usize)>,
+}
+
+impl Buckets {
+    fn new(keys: usize, mut anchored: Vec<(usize, usize, usize)>) -> Self {
+        anchored.sort_unstable_by_key(|(key, _, _)| *key);
+
+        let mut starts = vec![0; keys + 1];
+        for (key, _, _) in &anchored {
+            starts[key + 1] += 1;
+        }
+        for i in 0..keys {
+            starts[i + 1] += starts[i];
+        }
+
+        Self {
+            starts,
+            entries: anchored.into_iter().map(|(_, i, off)| (i, off)).collect(),
+        }
+    }
+
+    #[inline]
+    fn get(&self, key: usize) -> &[(usize, usize)] {
+        &self.entries[self.starts[key] as usize..self.starts[key + 1] as usize]
+    }
+}
+
+/// Set of patterns that are searched for in a single pass.
+/// Every pattern is put into a bucket by its rarest pair of adjacent exact bytes (or a single exact byte),
+/// so every position of the buffer is only checked against patterns that can actually start near it.
+/// ```
+/// # use faithe::pattern::{Pattern, PatternSet};
+/// let set = PatternSet::new([
+///     Pattern::from_ida_style("E8 ? ? ? ? 90"),
+///     Pattern::from_ida_style("C3"),
+///     Pattern::from_ida_style("90 C3"),
+/// ]);
+/// let data = [0xE8, 1, 2, 3, 4, 0x90, 0xC3];
+/// assert_eq!(set.find_iter(&data).collect::<Vec<_>>(), [(0, 0), (2, 5), (1, 6)]);
+/// ```
+pub struct PatternSet {
+    pub(super) patterns: Vec<Pattern>,
+    pairs: Buckets,
+    singles: Buckets,
+    /// Patterns that have no exact bytes and have to be checked at every position.
+    unanchored: Vec<usize>,
+    /// The biggest offset of an anchor inside of a pattern.
+    max_offset: usize,
+    /// Length of the longest pattern.
+    pub(super) max_len: usize,
+}
+
+impl PatternSet {
+    /// Compiles patterns into a set. Indices of the patterns are preserved in the reported matches.
+    pub fn new(patterns: impl IntoIterator<Item = Pattern>) -> Self {
+        let patterns = patterns.into_iter().collect::<Vec<_>>();
+        let rank = |b: u8| super::scan::BYTE_RANKS[b as usize] as usize;
+
+        let mut pairs = vec![];
+        let mut singles = vec![];
+        let mut unanchored = vec![];
+        let mut max_offset = 0;
+
+        for (i, pat) in patterns.iter().enumerate() {
+            let pair = pat
+                .bytes
+                .windows(2)
+                .enumerate()
+                .filter(|(_, w)| w[0].is_exact() && w[1].is_exact())
+                .min_by_key(|(_, w)| rank(w[0].value()) + rank(w[1].value()));
+
+            if let Some((off, w)) = pair {
+                pairs.push(((w[0].value() as usize) << 8 | w[1].value() as usize, i, off));
+                max_offset = max_offset.max(off);
+            } else if let Some((off, m)) = pat
+                .bytes
+                .iter()
+                .enumerate()
+                .filter(|(_, m)| m.is_exact())
+                .min_by_key(|(_, m)| rank(m.value()))
+            {
+                singles.push((m.value() as usize, i, off));
+                max_offset = max_offset.max(off);
+            } else if pat.len() != 0 {
+                unanchored.push(i);
+            }
+        }
+
+        Self {
+            max_len: patterns.iter().map(Pattern::len).max().unwrap_or(0),
+            pairs: Buckets::new(0x10000, pairs),
+            singles: Buckets::new(0x100, singles),
+            unanchored,
+            max_offset,
+            patterns,
+        }
+    }
+
+    /// Returns the amount of patterns in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.patterns.len()
+    }
+
+    /// Returns `true` if set contains no patterns.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Returns the pattern with index `idx`.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<&Pattern> {
+        self.patterns.get(idx)
+    }
+
+    /// Returns an iterator over all occurences of all patterns in `data`.
+    /// Yields `(pattern_index, offset)` sorted by offset and then by pattern index.
+    #[inline]
+    pub fn find_iter<'s, 'd>(&'s self, data: &'d [u8]) -> SetFindIter<'s, 'd> {
+        SetFindIter {
+            set: self,
+            data,
+            pos: 0,
+            pending: BinaryHeap::new(),
+        }
+    }
+}
+
+impl FromIterator<Pattern> for PatternSet {
+    fn from_iter<T: IntoIterator<Item = Pattern>>(iter: T) -> Self {
+        Self::new(iter)
+    }
+}
+
+/// Iterator over occurences of patterns from [`PatternSet`] in a byte slice.
+/// Created by [`PatternSet::find_iter`].
+pub struct SetFindIter<'s, 'd> {
+    set: &'s PatternSet,
+    data: &'d [u8],
+    /// Next position of the buffer to check anchors at.
+    pos: usize,
+    /// Verified matches that can't be reported yet, because some other pattern may still start before them.
+    pending: BinaryHeap<Reverse<(usize, usize)>>,
+}
+
+impl<'s, 'd> SetFindIter<'s, 'd> {
+    fn check(&mut self, entries: &[(usize, usize)]) {
+        for (idx, off) in entries.iter().copied() {
+            if let Some(start) = self.pos.checked_sub(off) {
+                let pat = &self.set.patterns[idx];
+                if self
+                    .data
+                    .get(start..start + pat.len())
+                    .is_some_and(|w| pat.matches(w))
+                {
+                    self.pending.push(Reverse((start, idx)));
+                }
+            }
+        }
+    }
+}
+
+impl<'s, 'd> Iterator for SetFindIter<'s, 'd> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let set = self.set;
+        loop {
+            // Anything found later will start at `pos - max_offset` or after it.
+            if let Some(Reverse((start, idx))) = self.pending.peek().copied() {
+                if start + set.max_offset < self.pos || self.pos >= self.data.len() {
+                    self.pending.pop();
+                    return Some((idx, start));
+                }
+            }
+            if self.pos >= self.data.len() {
+                return None;
+            }
+
+            let b = self.data[self.pos] as usize;
+            if let Some(next) = self.data.get(self.pos + 1) {
+                self.check(set.pairs.get(b << 8 | *next as usize));
+            }
+            self.check(set.singles.get(b));
+            for idx in set.unanchored.iter().copied() {
+                let pat = &set.patterns[idx];
+                if self
+                    .data
+                    .get(self.pos..self.pos + pat.len())
+                    .is_some_and(|w| pat.matches(w))
+                {
+                    self.pending.push(Reverse((self.pos, idx)));
+                }
+            }
+            self.pos += 1;
+        }
+    }
+}
+
+impl<'s, 'd> PatternSearcher<&'s PatternSet> for &'d [u8] {
+    type Output = (usize, usize);
+    type Iter = SetFindIter<'s, 'd>;
+
+    fn find_all(&self, set: &'s PatternSet) -> crate::Result<Self::Iter> {
+        Ok(set.find_iter(self))
+    }
+}
@@ -0,0 +1,207 @@
+extern crate alloc;
+use super::{ByteMatch, Pattern};
+use crate::FaitheError;
+use alloc::vec::Vec;
+use iced_x86::{Decoder, DecoderOptions, OpKind, Register};
+
+/// Generates the shortest unique pattern for an instruction in a code buffer.
+///
+/// Code is disassembled instruction by instruction starting at the target and the pattern is extended
+/// until it matches only once in the whole buffer.
+/// Bytes that are likely to change between builds are wildcarded:
+/// rel32 branch targets, rip-relative and absolute displacements and 32/64-bit immediates.
+/// ```
+/// # use faithe::pattern::SignatureBuilder;
+/// let code = [
+///     0x48, 0x8B, 0x05, 0x10, 0x00, 0x00, 0x00, // mov rax, [rip + 0x10]
+///     0xC3,                                     // ret
+///     0x48, 0x8B, 0x05, 0x20, 0x00, 0x00, 0x00, // mov rax, [rip + 0x20]
+///     0xE8, 0x00, 0x01, 0x00, 0x00,             // call 0x1114
+///     0xC3,                                     // ret
+/// ];
+/// let pat = SignatureBuilder::new(&code, 0x1000)
+///     .bitness(64)
+///     .build(0x1008)
+///     .unwrap();
+/// assert_eq!(pat.to_ida_style(), "48 8B 05 ? ? ? ? E8");
+/// assert_eq!(pat.to_peid_style(), "48 8B 05 ?? ?? ?? ?? E8");
+/// assert_eq!(pat.find_iter(&code).collect::<Vec<_>>(), [8]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SignatureBuilder<'a> {
+    code: &'a [u8],
+    base: usize,
+    bitness: u32,
+    max_len: usize,
+}
+
+impl<'a> SignatureBuilder<'a> {
+    /// Creates new builder for the code located at `base`.
+    /// Bitness of the code defaults to the bitness of the current target.
+    pub fn new(code: &'a [u8], base: usize) -> Self {
+        Self {
+            code,
+            base,
+            bitness: if cfg!(target_pointer_width = "64") {
+                64
+            } else {
+                32
+            },
+            max_len: 64,
+        }
+    }
+
+    /// Sets the bitness of the code, either `16`, `32` or `64`.
+    pub fn bitness(mut self, bitness: u32) -> Self {
+        self.bitness = bitness;
+        self
+    }
+
+    /// Sets the maximum length of the signature in bytes. Defaults to `64`.
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Builds the signature for the instruction at `address`.
+    /// # Errors
+    /// * [`FaitheError::InvalidInstruction`] if `address` is outside of the buffer or code at it can't be decoded.
+    /// * [`FaitheError::SignatureNotUnique`] if no unique pattern fits in the maximum length.
+    pub fn build(&self, address: usize) -> crate::Result<Pattern> {
+        let offset = address
+            .checked_sub(self.base)
+            .filter(|offset| *offset < self.code.len())
+            .ok_or(FaitheError::InvalidInstruction)?;
+        let code = &self.code[offset..];
+
+        let mut decoder =
+            Decoder::try_with_ip(self.bitness, code, address as u64, DecoderOptions::NONE)
+                .map_err(|_| FaitheError::InvalidInstruction)?;
+        let mut bytes = Vec::new();
+
+        while bytes.len() < self.max_len && decoder.can_decode() {
+            let start = bytes.len();
+            let instr = decoder.decode();
+            if instr.is_invalid() {
+                return Err(FaitheError::InvalidInstruction);
+            }
+            bytes.extend(
+                code[start..start + instr.len()]
+                    .iter()
+                    .copied()
+                    .map(ByteMatch::exact),
+            );
+
+            let offsets = decoder.get_constant_offsets(&instr);
+            let instr_bytes = &mut bytes[start..];
+            if offsets.has_displacement()
+                && (instr.is_ip_rel_memory_operand()
+                    || instr.memory_base() == Register::None && offsets.displacement_size() >= 4)
+            {
+                let at = offsets.displacement_offset();
+                instr_bytes[at..at + offsets.displacement_size()].fill(ByteMatch::ANY);
+            }
+            // Branch displacements are reported as immediates too.
+            if offsets.has_immediate() && offsets.immediate_size() >= 4 {
+                let at = offsets.immediate_offset();
+                instr_bytes[at..at + offsets.immediate_size()].fill(ByteMatch::ANY);
+            }
+
+            // Uniqueness only grows with the length, so the first unique prefix is the shortest one.
+            for len in start + 1..=bytes.len().min(self.max_len) {
+                if bytes[len - 1].is_any() {
+                    continue;
+                }
+                let pat = Pattern::from(bytes[..len].to_vec());
+                if pat.find_iter(self.code).nth(1).is_none() {
+                    return Ok(pat);
+                }
+            }
+        }
+        Err(FaitheError::SignatureNotUnique)
+    }
+}
+
+/// Resolves the address referred to by the instruction at the start of `code` located at `address`.
+/// Used by smart offsets and signatures.
+///
+/// * Near branches (`call`, `jmp`, `jcc`, `loop`, ...) resolve to their target.
+/// * Instructions with rip-relative memory operand (`lea`, `mov`, `cmp`, ...) resolve to the referenced address.
+/// * Instructions with absolute memory operand, e.g. `mov eax, [0x401000]` on 32-bit, resolve to its displacement.
+///
+/// Targets are relative to the end of the instruction, so its length is always taken into account.
+/// # Errors
+/// * [`FaitheError::InvalidInstruction`] if code can't be decoded.
+/// * [`FaitheError::NoInstructionTarget`] if instruction doesn't refer to any address.
+/// ```
+/// # use faithe::{pattern::instruction_target, FaitheError};
+/// // call 0x1105
+/// assert_eq!(instruction_target(&[0xE8, 0x00, 0x01, 0x00, 0x00], 0x1000, 64)?, 0x1105);
+/// // jmp short 0xFF2
+/// assert_eq!(instruction_target(&[0xEB, 0xF0], 0x1000, 64)?, 0xFF2);
+/// // jmp 0x1009
+/// assert_eq!(instruction_target(&[0xE9, 0x04, 0x00, 0x00, 0x00], 0x1000, 64)?, 0x1009);
+/// // je 0x1010
+/// assert_eq!(instruction_target(&[0x0F, 0x84, 0x0A, 0x00, 0x00, 0x00], 0x1000, 64)?, 0x1010);
+/// // jne short 0x1012
+/// assert_eq!(instruction_target(&[0x75, 0x10], 0x1000, 64)?, 0x1012);
+/// // lea rcx, [rip + 0x20]
+/// assert_eq!(instruction_target(&[0x48, 0x8D, 0x0D, 0x20, 0x00, 0x00, 0x00], 0x1000, 64)?, 0x1027);
+/// // mov rax, [rip - 0x10]
+/// assert_eq!(instruction_target(&[0x48, 0x8B, 0x05, 0xF0, 0xFF, 0xFF, 0xFF], 0x1000, 64)?, 0xFF7);
+/// // mov [rip + 0x100], eax
+/// assert_eq!(instruction_target(&[0x89, 0x05, 0x00, 0x01, 0x00, 0x00], 0x1000, 64)?, 0x1106);
+/// // cmp byte ptr [rip + 0x30], 0
+/// assert_eq!(instruction_target(&[0x80, 0x3D, 0x30, 0x00, 0x00, 0x00, 0x00], 0x1000, 64)?, 0x1037);
+/// // call qword ptr [rip + 0x40]
+/// assert_eq!(instruction_target(&[0xFF, 0x15, 0x40, 0x00, 0x00, 0x00], 0x1000, 64)?, 0x1046);
+///
+/// // mov eax, [0x401000]
+/// assert_eq!(instruction_target(&[0xA1, 0x00, 0x10, 0x40, 0x00], 0x1000, 32)?, 0x401000);
+/// // mov ecx, [0x402000]
+/// assert_eq!(instruction_target(&[0x8B, 0x0D, 0x00, 0x20, 0x40, 0x00], 0x1000, 32)?, 0x402000);
+/// // cmp dword ptr [0x403000], 1
+/// assert_eq!(instruction_target(&[0x83, 0x3D, 0x00, 0x30, 0x40, 0x00, 0x01], 0x1000, 32)?, 0x403000);
+/// // call 0x1105
+/// assert_eq!(instruction_target(&[0xE8, 0x00, 0x01, 0x00, 0x00], 0x1000, 32)?, 0x1105);
+///
+/// // mov rax, [rcx + 0x10]
+/// assert!(matches!(
+///     instruction_target(&[0x48, 0x8B, 0x41, 0x10], 0x1000, 64),
+///     Err(FaitheError::NoInstructionTarget)
+/// ));
+/// // ret
+/// assert!(matches!(
+///     instruction_target(&[0xC3], 0x1000, 64),
+///     Err(FaitheError::NoInstructionTarget)
+/// ));
+/// // Truncated `call`.
+/// assert!(matches!(
+///     instruction_target(&[0xE8, 0x00], 0x1000, 64),
+///     Err(FaitheError::InvalidInstruction)
+/// ));
+/// # Ok::<(), FaitheError>(())
+/// ```
+pub fn instruction_target(code: &[u8], address: usize, bitness: u32) -> crate::Result<usize> {
+    let asm = Decoder::try_with_ip(bitness, code, address as u64, DecoderOptions::NONE)
+        .map_err(|_| FaitheError::InvalidInstruction)?
+        .decode();
+    if asm.is_invalid() {
+        return Err(FaitheError::InvalidInstruction);
+    }
+
+    let has_memory = (0..asm.op_count()).any(|i| asm.op_kind(i) == OpKind::Memory);
+    match asm.op0_kind() {
+        OpKind::NearBranch16 | OpKind::NearBranch32 | OpKind::NearBranch64 => {
+            Ok(asm.near_branch_target() as usize)
+        }
+        _ if has_memory && asm.is_ip_rel_memory_operand() => Ok(asm.ip_rel_memory_address() as usize),
+        _ if has_memory
+            && asm.memory_base() == Register::None
+            && asm.memory_index() == Register::None =>
+        {
+            Ok(asm.memory_displacement64() as usize)
+        }
+        _ => Err(FaitheError::NoInstructionTarget),
+    }
+}
@@ -0,0 +1,116 @@
+use super::{ByteMatch, Pattern};
+use crate::memory::Pod;
+use core::mem::size_of;
+
+/// Byte order of the values searched with [`Pattern::from_value`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endian {
+    /// Least significant byte first, used by x86.
+    Little,
+    /// Most significant byte first.
+    Big,
+}
+
+impl Endian {
+    /// Byte order of the target the crate is compiled for.
+    #[cfg(target_endian = "little")]
+    pub const NATIVE: Self = Self::Little;
+    /// Byte order of the target the crate is compiled for.
+    #[cfg(target_endian = "big")]
+    pub const NATIVE: Self = Self::Big;
+}
+
+/// Matches ASCII letters in both cases, other bytes exactly.
+fn ignore_case(b: u8) -> ByteMatch {
+    if b.is_ascii_alphabetic() {
+        // Cases of ASCII letters only differ in the bit `0x20`.
+        ByteMatch::masked(b, !0x20)
+    } else {
+        ByteMatch::exact(b)
+    }
+}
+
+impl Pattern {
+    /// Creates pattern that matches UTF-8 bytes of the string.
+    /// ```
+    /// # use faithe::pattern::Pattern;
+    /// let pat = Pattern::from_str_utf8("Hello");
+    /// assert_eq!(pat.find_iter(b"Say Hello").next(), Some(4));
+    /// ```
+    pub fn from_str_utf8(s: impl AsRef<str>) -> Self {
+        s.as_ref().bytes().map(ByteMatch::exact).collect()
+    }
+
+    /// Creates pattern that matches UTF-16LE code units of the string, as stored by `wide!`.
+    /// ```
+    /// # use faithe::pattern::Pattern;
+    /// let pat = Pattern::from_str_utf16("Hi");
+    /// assert_eq!(pat.find_iter(&[0, b'H', 0, b'i', 0]).next(), Some(1));
+    /// ```
+    pub fn from_str_utf16(s: impl AsRef<str>) -> Self {
+        s.as_ref()
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .map(ByteMatch::exact)
+            .collect()
+    }
+
+    /// Creates pattern that matches UTF-8 bytes of the string, ASCII letters match in any case.
+    /// ```
+    /// # use faithe::pattern::Pattern;
+    /// let pat = Pattern::from_str_utf8_ignore_case("kernel32.dll");
+    /// assert_eq!(pat.find_iter(b"KERNEL32.DLL").next(), Some(0));
+    /// assert_eq!(pat.find_iter(b"KERNEL32_DLL").next(), None);
+    /// ```
+    pub fn from_str_utf8_ignore_case(s: impl AsRef<str>) -> Self {
+        s.as_ref().bytes().map(ignore_case).collect()
+    }
+
+    /// Creates pattern that matches UTF-16LE code units of the string, ASCII letters match in any case.
+    /// ```
+    /// # use faithe::pattern::Pattern;
+    /// let pat = Pattern::from_str_utf16_ignore_case("Ok");
+    /// assert_eq!(pat.find_iter(&[b'o', 0, b'K', 0]).next(), Some(0));
+    /// ```
+    pub fn from_str_utf16_ignore_case(s: impl AsRef<str>) -> Self {
+        s.as_ref()
+            .encode_utf16()
+            .flat_map(|c| {
+                let [lo, hi] = c.to_le_bytes();
+                let lo = if hi == 0 {
+                    ignore_case(lo)
+                } else {
+                    ByteMatch::exact(lo)
+                };
+                [lo, ByteMatch::exact(hi)]
+            })
+            .collect()
+    }
+
+    /// Creates pattern that matches the bytes of the value stored with `endian` byte order.
+    /// Value is treated as a single number, so with non-native byte order all of its bytes are reversed.
+    /// `T` is [`Pod`], so all of its bytes are initialized.
+    /// ```
+    /// # use faithe::pattern::{Endian, Pattern};
+    /// let pat = Pattern::from_value(0x11223344u32, Endian::Little);
+    /// assert_eq!(pat.find_iter(&[0, 0x44, 0x33, 0x22, 0x11]).next(), Some(1));
+    ///
+    /// let pat = Pattern::from_value(1.0f32, Endian::Big);
+    /// assert_eq!(pat.find_iter(&[0x3F, 0x80, 0, 0]).next(), Some(0));
+    /// ```
+    /// Types that may contain padding are rejected.
+    /// ```compile_fail
+    /// # use faithe::pattern::{Endian, Pattern};
+    /// let pat = Pattern::from_value((1u8, 2u32), Endian::Little);
+    /// ```
+    pub fn from_value<T: Pod>(value: T, endian: Endian) -> Self {
+        let bytes =
+            unsafe { core::slice::from_raw_parts(&value as *const T as *const u8, size_of::<T>()) };
+
+        if endian == Endian::NATIVE {
+            bytes.iter().copied().map(ByteMatch::exact).collect()
+        } else {
+            bytes.iter().rev().copied().map(ByteMatch::exact).collect()
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use super::OwnedProcess;
+use crate::{
+    module::ModuleIterator, thread::ThreadIterator, types::access_rights::PROCESS_ACCESS_RIGHTS,
+};
+
+/// Basic information about single process.
+#[derive(Debug, Clone)]
+pub struct ProcessEntry {
+    /// Process's id
+    pub process_id: u32,
+    /// Number of running threads in the process.
+    pub cnt_threads: u32,
+    /// Id of parent process.
+    pub parent_id: u32,
+    /// Thread priority for any newly created thread.
+    pub thread_base_priority: i32,
+    /// Name of an executable file.
+    /// On Linux this is `comm` of the process, which is truncated to 15 bytes.
+    pub file_name: String,
+}
+
+impl ProcessEntry {
+    /// Returns an iterator over loaded modules in the process.
+    pub fn modules(&self) -> crate::Result<ModuleIterator> {
+        ModuleIterator::new(self.process_id)
+    }
+
+    /// Returns an iterator over running threads in the process.
+    pub fn threads(&self) -> crate::Result<ThreadIterator> {
+        ThreadIterator::new(self.process_id)
+    }
+}
+
+impl ProcessEntry {
+    /// Tries to open this particular process.
+    pub fn open(
+        &self,
+        inherit_handle: bool,
+        desired_access: PROCESS_ACCESS_RIGHTS,
+    ) -> crate::Result<OwnedProcess> {
+        OwnedProcess::open_by_id(self.process_id, inherit_handle, desired_access)
+    }
+}
@@ -1,55 +1,14 @@
-use super::OwnedProcess;
-use crate::{module::ModuleIterator, thread::ThreadIterator, FaitheError};
+use super::ProcessEntry;
+use crate::FaitheError;
 use std::mem::size_of;
 use windows::Win32::{
     Foundation::HANDLE,
-    System::{
-        Diagnostics::ToolHelp::{
-            CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W,
-            TH32CS_SNAPPROCESS,
-        },
-        Threading::PROCESS_ACCESS_RIGHTS,
+    System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W,
+        TH32CS_SNAPPROCESS,
     },
 };
 
-/// Basic information about single process.
-#[derive(Debug, Clone)]
-pub struct ProcessEntry {
-    /// Process's id
-    pub process_id: u32,
-    /// Number of running threads in the process.
-    pub cnt_threads: u32,
-    /// Id of parent process.
-    pub parent_id: u32,
-    /// Thread priority for any newly created thread.
-    pub thread_base_priority: i32,
-    /// Name of an executable file.
-    pub file_name: String,
-}
-
-impl ProcessEntry {
-    /// Returns an iterator over loaded modules in the process.
-    pub fn modules(&self) -> crate::Result<ModuleIterator> {
-        ModuleIterator::new(self.process_id)
-    }
-
-    /// Returns an iterator over running threads in the process.
-    pub fn threads(&self) -> crate::Result<ThreadIterator> {
-        ThreadIterator::new(self.process_id)
-    }
-}
-
-impl ProcessEntry {
-    /// Tries to open this particular process.
-    pub fn open(
-        &self,
-        inherit_handle: bool,
-        desired_access: PROCESS_ACCESS_RIGHTS,
-    ) -> crate::Result<OwnedProcess> {
-        OwnedProcess::open_by_id(self.process_id, inherit_handle, desired_access)
-    }
-}
-
 impl From<PROCESSENTRY32W> for ProcessEntry {
     fn from(pe: PROCESSENTRY32W) -> Self {
         Self {
@@ -0,0 +1,44 @@
+use crate::{process::ProcessEntry, procfs};
+use std::path::Path;
+
+/// Iterator over all running processes.
+/// Processes are listed when the iterator is created, their `/proc/pid/stat` is read as they are reached,
+/// processes that exited by then are skipped.
+/// ```
+/// # use faithe::process::ProcessIterator;
+/// let current = ProcessIterator::new()?
+///     .find(|p| p.process_id == std::process::id())
+///     .unwrap();
+/// assert!(current.cnt_threads >= 1);
+/// assert_eq!(current.parent_id, std::os::unix::process::parent_id());
+/// # Ok::<(), faithe::FaitheError>(())
+/// ```
+pub struct ProcessIterator {
+    pids: std::vec::IntoIter<u32>,
+}
+
+impl ProcessIterator {
+    /// Creates new iterator over processes
+    pub fn new() -> crate::Result<Self> {
+        Ok(Self {
+            pids: procfs::ids(Path::new("/proc"))?.into_iter(),
+        })
+    }
+}
+
+impl Iterator for ProcessIterator {
+    type Item = ProcessEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.pids.find_map(|pid| {
+            let stat = procfs::stat(&procfs::proc_dir(pid).join("stat")).ok()?;
+            Some(ProcessEntry {
+                process_id: pid,
+                cnt_threads: stat.num_threads,
+                parent_id: stat.parent_id,
+                thread_base_priority: stat.priority,
+                file_name: stat.comm,
+            })
+        })
+    }
+}
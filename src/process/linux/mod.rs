@@ -0,0 +1,5 @@
+mod iter;
+pub use iter::*;
+mod proc;
+pub use proc::*;
+mod regions;
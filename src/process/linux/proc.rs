@@ -0,0 +1,234 @@
+use super::ProcessIterator;
+use crate::{
+    memory::{MemoryReader, MemoryWriter},
+    procfs::proc_dir,
+    types::access_rights::{PROCESS_ACCESS_RIGHTS, PROCESS_VM_OPERATION, PROCESS_VM_WRITE},
+    FaitheError,
+};
+use std::{
+    fs::{self, File, OpenOptions},
+    io,
+    mem::{size_of, zeroed},
+    os::unix::{fs::FileExt, io::AsRawFd},
+    path::{Path, PathBuf},
+};
+
+/// Represents an opened process.
+/// Memory is accessed with `process_vm_readv` and `process_vm_writev`,
+/// `/proc/pid/mem` is used instead if they are not permitted, e.g. by seccomp.
+/// ```
+/// # use faithe::{process::OwnedProcess, types::access_rights::PROCESS_ALL_ACCESS};
+/// use faithe::pattern::{Endian, Pattern};
+///
+/// let mut child = std::process::Command::new("sleep").arg("30").spawn()?;
+/// let process = OwnedProcess::open_by_id(child.id(), false, PROCESS_ALL_ACCESS)?;
+/// assert_eq!(process.id(), child.id());
+/// assert_eq!(process.image_name().as_deref(), Some("sleep"));
+///
+/// // Child may still be loading its executable.
+/// let image = loop {
+///     match process.modules()?.find(|m| m.name == "sleep") {
+///         Some(module) => break module.base_address,
+///         None => std::thread::yield_now(),
+///     }
+/// };
+/// // Executable starts with ELF header.
+/// assert_eq!(process.read::<[u8; 4]>(image)?, *b"\x7FELF");
+///
+/// let maps = std::fs::read_to_string(format!("/proc/{}/maps", child.id()))?;
+/// let stack = maps.lines().find(|l| l.ends_with("[stack]")).unwrap();
+/// let stack = usize::from_str_radix(stack.split_once('-').unwrap().0, 16)?;
+/// assert_eq!(process.write(stack, 0xDEADBEEFu32)?, 4);
+/// assert_eq!(process.read::<u32>(stack)?, 0xDEADBEEF);
+/// assert_eq!(process.follow_pointer_path(stack, &[0x10])?, stack + 0x10);
+/// assert!(process.read::<u32>(0).is_err());
+///
+/// // Whole address space is scanned, unreadable pages are skipped.
+/// let pat = Pattern::from_value(0xDEADBEEFu32, Endian::NATIVE);
+/// assert!(process.scan_memory(&pat).any(|address| address == stack));
+/// # #[cfg(feature = "rayon")]
+/// assert!(process.par_scan_memory(&pat).contains(&stack));
+///
+/// child.kill()?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct OwnedProcess {
+    pid: libc::pid_t,
+    /// `/proc/pid/mem`, `None` if it could not be opened.
+    mem: Option<File>,
+}
+
+impl OwnedProcess {
+    /// Opens process by it's id.
+    /// `/proc/pid/mem` is opened for writing if `PROCESS_VM_WRITE` or `PROCESS_VM_OPERATION` is requested
+    /// and is inherited by child processes if `inherit_handle` is `true`.
+    pub fn open_by_id(
+        id: u32,
+        inherit_handle: bool,
+        desired_access: PROCESS_ACCESS_RIGHTS,
+    ) -> crate::Result<Self> {
+        let dir = proc_dir(id);
+        if !dir.is_dir() {
+            return Err(FaitheError::ProcessNotFound);
+        }
+
+        let write = desired_access.contains(PROCESS_VM_WRITE)
+            || desired_access.contains(PROCESS_VM_OPERATION);
+        let mem = OpenOptions::new()
+            .read(true)
+            .write(write)
+            .open(dir.join("mem"))
+            .ok();
+        if let (Some(mem), true) = (&mem, inherit_handle) {
+            // Files are always opened with `O_CLOEXEC`.
+            if unsafe { libc::fcntl(mem.as_raw_fd(), libc::F_SETFD, 0) } == -1 {
+                return Err(FaitheError::last_error());
+            }
+        }
+
+        Ok(Self { pid: id as _, mem })
+    }
+
+    /// Searches for runing processes and opens one if found.
+    /// Process matches if its executable's file name or its `comm` is equal to `name`.
+    pub fn open_by_name(
+        name: impl AsRef<str>,
+        inherit_handle: bool,
+        desired_access: PROCESS_ACCESS_RIGHTS,
+    ) -> crate::Result<Self> {
+        let name = name.as_ref();
+        ProcessIterator::new()?
+            .find(|pe| {
+                pe.file_name == name
+                    || exe(pe.process_id)
+                        .ok()
+                        .and_then(|p| file_name(&p))
+                        .as_deref()
+                        == Some(name)
+            })
+            .ok_or(FaitheError::ProcessNotFound)?
+            .open(inherit_handle, desired_access)
+    }
+
+    /// Returns process's id.
+    pub fn id(&self) -> u32 {
+        self.pid as _
+    }
+
+    /// Retrieves process's image file name.
+    /// Falls back to `/proc/pid/comm` if the executable can't be resolved, e.g. for kernel threads.
+    pub fn image_name(&self) -> Option<String> {
+        exe(self.id())
+            .ok()
+            .and_then(|p| file_name(&p))
+            .or_else(|| comm(self.id()))
+    }
+
+    /// Retrieves full path to process's executable.
+    pub fn path(&self) -> crate::Result<String> {
+        Ok(exe(self.id())?.to_string_lossy().into_owned())
+    }
+
+    /// Reads process's memory at address and returns read value.
+    pub fn read<T>(&self, address: usize) -> crate::Result<T> {
+        unsafe {
+            let mut value = zeroed();
+            let buf =
+                std::slice::from_raw_parts_mut(&mut value as *mut T as *mut u8, size_of::<T>());
+            if self.read_buf(address, buf)? == size_of::<T>() {
+                Ok(value)
+            } else {
+                Err(FaitheError::QueryFailed)
+            }
+        }
+    }
+
+    /// Reads process's memory at address and copy `buf.len()` bytes into buffer.
+    /// Returns the amount of bytes read.
+    pub fn read_buf(&self, address: usize, mut buf: impl AsMut<[u8]>) -> crate::Result<usize> {
+        let buf = buf.as_mut();
+        let local = libc::iovec {
+            iov_base: buf.as_mut_ptr() as _,
+            iov_len: buf.len(),
+        };
+        let remote = libc::iovec {
+            iov_base: address as _,
+            iov_len: buf.len(),
+        };
+
+        let read = unsafe { libc::process_vm_readv(self.pid, &local, 1, &remote, 1, 0) };
+        if read >= 0 {
+            Ok(read as usize)
+        } else {
+            self.fallback(|mem| mem.read_at(buf, address as u64))
+        }
+    }
+
+    /// Writes process's memory at address by copying value into the target memory.
+    /// Returns the amount of bytes written.
+    pub fn write<T>(&self, address: usize, value: T) -> crate::Result<usize>
+    where
+        T: Clone,
+    {
+        let buf =
+            unsafe { std::slice::from_raw_parts(&value as *const T as *const u8, size_of::<T>()) };
+        self.write_buf(address, buf)
+    }
+
+    /// Writes process's memory at address by copying whole buffer into the target memory.
+    /// Returns the amount of bytes written.
+    pub fn write_buf(&self, address: usize, buf: impl AsRef<[u8]>) -> crate::Result<usize> {
+        let buf = buf.as_ref();
+        let local = libc::iovec {
+            iov_base: buf.as_ptr() as _,
+            iov_len: buf.len(),
+        };
+        let remote = libc::iovec {
+            iov_base: address as _,
+            iov_len: buf.len(),
+        };
+
+        let written = unsafe { libc::process_vm_writev(self.pid, &local, 1, &remote, 1, 0) };
+        if written >= 0 {
+            Ok(written as usize)
+        } else {
+            self.fallback(|mem| mem.write_at(buf, address as u64))
+        }
+    }
+
+    /// Retries failed `process_vm_*` call through `/proc/pid/mem` if the syscall is not permitted.
+    fn fallback(&self, access: impl FnOnce(&File) -> io::Result<usize>) -> crate::Result<usize> {
+        let error = io::Error::last_os_error();
+        match (&self.mem, error.raw_os_error()) {
+            (Some(mem), Some(libc::ENOSYS | libc::EPERM)) => Ok(access(mem)?),
+            _ => Err(error.into()),
+        }
+    }
+}
+
+impl MemoryReader for OwnedProcess {
+    #[inline]
+    fn read_bytes(&self, address: usize, buf: &mut [u8]) -> crate::Result<usize> {
+        self.read_buf(address, buf)
+    }
+}
+
+impl MemoryWriter for OwnedProcess {
+    #[inline]
+    fn write_bytes(&mut self, address: usize, buf: &[u8]) -> crate::Result<usize> {
+        self.write_buf(address, buf)
+    }
+}
+
+fn exe(pid: u32) -> io::Result<PathBuf> {
+    fs::read_link(proc_dir(pid).join("exe"))
+}
+
+fn comm(pid: u32) -> Option<String> {
+    let comm = fs::read_to_string(proc_dir(pid).join("comm")).ok()?;
+    Some(comm.trim_end_matches('\n').to_string())
+}
+
+fn file_name(path: &Path) -> Option<String> {
+    Some(path.file_name()?.to_string_lossy().into_owned())
+}
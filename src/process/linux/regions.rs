@@ -0,0 +1,66 @@
+use super::OwnedProcess;
+use crate::{
+    memory::MemoryQuery,
+    process::{MemoryRegion, MemoryRegionIter},
+    procfs,
+};
+use std::ops::Range;
+
+impl OwnedProcess {
+    /// Returns process's memory regions with their resident and dirty sizes from `/proc/pid/smaps`.
+    /// Reading `smaps` is much slower than listing regions with [`Self::regions`].
+    pub fn regions_with_usage(&self) -> crate::Result<Vec<MemoryRegion>> {
+        Ok(procfs::regions_with_usage(self.id())?)
+    }
+}
+
+/// Regions are listed from `/proc/pid/maps` at once, querying a single region reads the whole file.
+/// ```
+/// # use faithe::{process::{OwnedProcess, RegionKind}, types::access_rights::PROCESS_VM_READ};
+/// # use faithe::types::MemoryProtection;
+/// let process = OwnedProcess::open_by_id(std::process::id(), false, PROCESS_VM_READ)?;
+/// let value = 5u32;
+/// let address = &value as *const u32 as usize;
+///
+/// let stack = process.regions().find(|r| r.kind == RegionKind::Stack).unwrap();
+/// assert!(stack.protection.contains(MemoryProtection::READ_WRITE));
+///
+/// let exe = std::env::current_exe()?;
+/// let image = process
+///     .regions()
+///     .find(|r| r.path.as_deref() == exe.to_str() && r.protection.contains(MemoryProtection::EXECUTE))
+///     .unwrap();
+/// assert_eq!(image.kind, RegionKind::Image);
+/// assert!(process.query().execute_at(image.start));
+/// assert_eq!(process.query().base(image.start), process.modules()?.next().map(|m| m.base_address));
+///
+/// assert!(process.query().read_at(address) && process.query().write_at(address));
+/// assert!(!process.query().read_at(0));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+impl MemoryQuery for OwnedProcess {
+    /// Addresses between mappings are returned as [`RegionKind::Free`] regions.
+    fn query_region(&self, address: usize) -> crate::Result<MemoryRegion> {
+        procfs::query(self.id(), address)
+    }
+
+    #[inline]
+    fn regions(&self) -> MemoryRegionIter<'_, Self> {
+        MemoryRegionIter::listed(self, procfs::regions(self.id()).unwrap_or_default())
+    }
+
+    fn readable_ranges(&self, from: usize, to: usize) -> Vec<Range<usize>> {
+        let mut ranges: Vec<Range<usize>> = vec![];
+        for region in MemoryQuery::regions(self).filter(MemoryRegion::is_readable) {
+            let (start, end) = (region.start.max(from), region.end.min(to));
+            if start >= end {
+                continue;
+            }
+            match ranges.last_mut() {
+                Some(last) if last.end == start => last.end = end,
+                _ => ranges.push(start..end),
+            }
+        }
+        ranges
+    }
+}
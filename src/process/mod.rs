@@ -1,8 +1,19 @@
-mod iter;
-pub use iter::*;
-mod proc;
-pub use proc::*;
+mod entry;
+pub use entry::*;
 mod query;
 pub use query::*;
 mod regions;
 pub use regions::*;
+mod shared;
+
+cfg_if::cfg_if! {
+    if #[cfg(windows)] {
+        mod iter;
+        pub use iter::*;
+        mod proc;
+        pub use proc::*;
+    } else {
+        mod linux;
+        pub use linux::*;
+    }
+}
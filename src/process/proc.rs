@@ -1,9 +1,7 @@
-use super::{MemoryRegionIter, ProcessIterator, Query};
+use super::{MemoryRegion, ProcessIterator};
 use crate::{
-    module::ModuleIterator,
-    pattern::{Pattern, PatternSearcher},
+    memory::{MemoryQuery, MemoryReader, MemoryWriter},
     size_of,
-    thread::ThreadIterator,
     types::{MemoryBasicInformation, MemoryProtection},
     FaitheError,
 };
@@ -66,18 +64,6 @@ impl OwnedProcess {
             .ok_or(FaitheError::ProcessNotFound)?
     }
 
-    /// Returns an iterator over all modules in the process.
-    pub fn modules(&self) -> crate::Result<ModuleIterator> {
-        ModuleIterator::new(self.id())
-    }
-
-    /// Returns an iterator over running threads in the process.
-    /// **Note**
-    /// Unlike Windows API, this iterator iterates only over process's threads.
-    pub fn threads(&self) -> crate::Result<ThreadIterator> {
-        ThreadIterator::new(self.id())
-    }
-
     /// Returns process's id.
     pub fn id(&self) -> u32 {
         unsafe { GetProcessId(self.0) }
@@ -100,12 +86,6 @@ impl OwnedProcess {
         }
     }
 
-    // @TODO: Fix iterator sometimes missing some memory regions??
-    /// Returns an itertor over process's allocated memory pages
-    pub fn regions(&self) -> MemoryRegionIter {
-        MemoryRegionIter::new(self)
-    }
-
     /// Returns the handle to the process.
     /// # Safety
     /// Do not close it and you will be alright.
@@ -125,19 +105,6 @@ impl OwnedProcess {
         self.module_name(self.query().base(address).ok_or(FaitheError::QueryFailed)?)
     }
 
-    /// Folows offsets' path, returning a pointer to an offset after.
-    pub fn follow_pointer_path(&self, mut base: usize, offsets: &[usize]) -> crate::Result<usize> {
-        for (i, offset) in offsets.iter().copied().enumerate() {
-            if i == offsets.len() - 1 {
-                return Ok(base + offset);
-            }
-
-            base = self.read(base + offset)?;
-        }
-
-        unreachable!()
-    }
-
     /// Retrieves full path to process's executable.
     pub fn path(&self) -> crate::Result<String> {
         unsafe {
@@ -152,20 +119,6 @@ impl OwnedProcess {
         }
     }
 
-    /// Searches for a specific pattern in the process's module.
-    /// Returns `None` if failed to find specified pattern.
-    /// Otherwise returns the address of the first occurence.
-    pub fn find_pattern(
-        &self,
-        mod_name: impl AsRef<str>,
-        pat: Pattern,
-    ) -> crate::Result<Option<usize>> {
-        self.modules()?
-            .find(|me| me.name == mod_name.as_ref())
-            .ok_or(FaitheError::ModuleNotFound)?
-            .find_first(pat)
-    }
-
     /// Reads process's memory at address and returns read value.
     pub fn read<T>(&self, address: usize) -> crate::Result<T> {
         unsafe {
@@ -424,10 +377,26 @@ impl OwnedProcess {
         let path: &Path = path.as_ref();
         Ok(path.file_name().unwrap().to_string_lossy().into_owned())
     }
+}
+
+impl MemoryReader for OwnedProcess {
+    #[inline]
+    fn read_bytes(&self, address: usize, buf: &mut [u8]) -> crate::Result<usize> {
+        self.read_buf(address, buf)
+    }
+}
+
+impl MemoryWriter for OwnedProcess {
+    #[inline]
+    fn write_bytes(&mut self, address: usize, buf: &[u8]) -> crate::Result<usize> {
+        self.write_buf(address, buf)
+    }
+}
 
-    /// Advanced memory querying
-    pub fn query(&self) -> Query {
-        Query(self)
+impl MemoryQuery for OwnedProcess {
+    #[inline]
+    fn query_region(&self, address: usize) -> crate::Result<MemoryRegion> {
+        self.query_memory(address).map(MemoryBasicInformation::into)
     }
 }
 
@@ -1,51 +1,57 @@
 use super::OwnedProcess;
-use windows::Win32::System::Memory::PAGE_PROTECTION_FLAGS;
+use crate::memory::MemoryQuery;
+pub use crate::memory::{MemoryRegion, RegionKind};
 
-#[derive(Debug)]
-/// Allocated memory page
-pub struct MemoryRegion {
-    /// Start of the page
-    pub start: usize,
-    /// End of the region
-    pub end: usize,
-    /// Size of the region
-    pub size: usize,
-    /// Protection of the region
-    pub protection: PAGE_PROTECTION_FLAGS,
-    /// Initial protection of the region
-    pub initial: PAGE_PROTECTION_FLAGS,
-}
-
-/// Iterator over process's memory regions
-pub struct MemoryRegionIter<'a> {
-    proc: &'a OwnedProcess,
+/// Iterator over allocated memory regions of a process.
+/// ```no_run
+/// # use faithe::{process::OwnedProcess, types::access_rights::PROCESS_ALL_ACCESS};
+/// let process = OwnedProcess::open_by_id(std::process::id(), false, PROCESS_ALL_ACCESS)?;
+/// for region in process.regions().filter(|r| r.is_readable()) {
+///     println!("{:#X}..{:#X} {:?} {:?}", region.start, region.end, region.kind, region.path);
+/// }
+/// # Ok::<(), faithe::FaitheError>(())
+/// ```
+pub struct MemoryRegionIter<'a, Q: MemoryQuery = OwnedProcess> {
+    source: &'a Q,
     current: usize,
+    /// Regions that were listed at once instead of being queried one by one.
+    listed: Option<std::vec::IntoIter<MemoryRegion>>,
 }
 
-impl<'a> MemoryRegionIter<'a> {
-    /// Creates new iterator over process's memory regions.
-    pub fn new(proc: &'a OwnedProcess) -> Self {
-        Self { current: 0, proc }
+impl<'a, Q: MemoryQuery> MemoryRegionIter<'a, Q> {
+    /// Creates new iterator over memory regions.
+    pub fn new(source: &'a Q) -> Self {
+        Self {
+            current: 0,
+            source,
+            listed: None,
+        }
+    }
+
+    /// Creates new iterator over regions that were already listed, e.g. from `/proc/pid/maps`.
+    pub fn listed(source: &'a Q, regions: Vec<MemoryRegion>) -> Self {
+        Self {
+            current: 0,
+            source,
+            listed: Some(regions.into_iter()),
+        }
     }
 }
 
-impl<'a> Iterator for MemoryRegionIter<'a> {
+impl<'a, Q: MemoryQuery> Iterator for MemoryRegionIter<'a, Q> {
     type Item = MemoryRegion;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let mut chunk = self.proc.query_memory(self.current).ok()?;
-        while chunk.state.0 == 0x10000 {
-            self.current = chunk.base_address + chunk.region_size;
-            chunk = self.proc.query_memory(self.current).ok()?;
+        if let Some(listed) = &mut self.listed {
+            return listed.find(|r| r.kind != RegionKind::Free);
+        }
+
+        let mut region = self.source.query_region(self.current).ok()?;
+        while region.kind == RegionKind::Free {
+            self.current = region.end;
+            region = self.source.query_region(self.current).ok()?;
         }
-        let region = MemoryRegion {
-            start: chunk.base_address,
-            end: chunk.base_address + chunk.region_size,
-            size: chunk.region_size,
-            protection: chunk.protection,
-            initial: chunk.alloc_protection,
-        };
-        self.current = chunk.base_address + chunk.region_size;
+        self.current = region.end;
         Some(region)
     }
 }
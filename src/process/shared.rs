@@ -0,0 +1,109 @@
+use super::{MemoryRegion, MemoryRegionIter, OwnedProcess, Query};
+use crate::{
+    memory::{MemoryQuery, MemoryReader},
+    module::ModuleIterator,
+    pattern::{ChunkFindIter, ChunkScanner, Needle, Pattern, PatternMatch, PatternSearcher},
+    thread::ThreadIterator,
+    FaitheError,
+};
+use std::ops::Range;
+
+/// Methods that work the same way on every platform.
+impl OwnedProcess {
+    /// Returns an iterator over all modules in the process.
+    pub fn modules(&self) -> crate::Result<ModuleIterator> {
+        ModuleIterator::new(self.id())
+    }
+
+    /// Returns an iterator over running threads in the process.
+    /// **Note**
+    /// Unlike Windows API, this iterator iterates only over process's threads.
+    pub fn threads(&self) -> crate::Result<ThreadIterator> {
+        ThreadIterator::new(self.id())
+    }
+
+    // @TODO: Fix iterator sometimes missing some memory regions??
+    /// Returns an itertor over process's allocated memory pages
+    #[inline]
+    pub fn regions(&self) -> MemoryRegionIter<'_> {
+        MemoryQuery::regions(self)
+    }
+
+    /// Advanced memory querying
+    pub fn query(&self) -> Query<'_> {
+        Query(self)
+    }
+
+    /// Returns continuous readable parts of memory from `from` to `to`.
+    /// Uncommitted, guard and no access pages are skipped.
+    #[inline]
+    pub fn readable_ranges(&self, from: usize, to: usize) -> Vec<Range<usize>> {
+        MemoryQuery::readable_ranges(self, from, to)
+    }
+
+    /// Folows offsets' path, returning a pointer to an offset after.
+    #[inline]
+    pub fn follow_pointer_path(&self, base: usize, offsets: &[usize]) -> crate::Result<usize> {
+        MemoryReader::follow_pointer_path(self, base, offsets)
+    }
+
+    /// Searches for a specific pattern in the process's module.
+    /// Returns `None` if failed to find specified pattern.
+    /// Otherwise returns the address of the first occurence.
+    pub fn find_pattern(
+        &self,
+        mod_name: impl AsRef<str>,
+        pat: Pattern,
+    ) -> crate::Result<Option<usize>> {
+        self.modules()?
+            .find(|me| me.name == mod_name.as_ref())
+            .ok_or(FaitheError::ModuleNotFound)?
+            .find_first(pat)
+    }
+
+    /// Searches for a specific pattern in the process's module and extracts its captured values.
+    /// Returns `None` if failed to find specified pattern.
+    /// Otherwise returns the first occurence, relative captures are resolved against its address.
+    pub fn find_pattern_match(
+        &self,
+        mod_name: impl AsRef<str>,
+        pat: &Pattern,
+    ) -> crate::Result<Option<PatternMatch>> {
+        if let Some(address) = self.find_pattern(mod_name, pat.clone())? {
+            let mut buf = vec![0; pat.len()];
+            self.read_buf(address, &mut buf)?;
+            Ok(pat.match_at(&buf, address))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Searches the whole address space of the process for a [`Pattern`](crate::pattern::Pattern)
+    /// or a [`PatternSet`](crate::pattern::PatternSet).
+    /// Memory is read in big chunks, uncommitted, guard and no access pages are skipped.
+    pub fn scan_memory<N: Needle>(&self, needle: N) -> ChunkFindIter<&Self, N> {
+        ChunkScanner::new(self).find_iter(
+            needle,
+            self.regions()
+                .filter(MemoryRegion::is_readable)
+                .map(|r| r.start..r.end),
+        )
+    }
+
+    /// Searches the whole address space of the process the same way [`Self::scan_memory`] does,
+    /// but chunks are read and scanned by all threads of the `rayon` pool.
+    /// Occurences are returned in the order of their addresses.
+    #[cfg(feature = "rayon")]
+    pub fn par_scan_memory<N>(&self, needle: N) -> Vec<N::Output>
+    where
+        N: Needle + Sync,
+        N::Output: Send,
+    {
+        ChunkScanner::new(self).par_find_all(
+            needle,
+            self.regions()
+                .filter(MemoryRegion::is_readable)
+                .map(|r| r.start..r.end),
+        )
+    }
+}
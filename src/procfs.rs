@@ -0,0 +1,237 @@
+use crate::{
+    memory::{MemoryRegion, RegionKind},
+    types::MemoryProtection,
+    FaitheError,
+};
+use std::{
+    collections::HashMap,
+    fs, io,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+/// Returns the directory of the process in `/proc`.
+pub(crate) fn proc_dir(pid: u32) -> PathBuf {
+    Path::new("/proc").join(pid.to_string())
+}
+
+fn invalid(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("malformed {}", path.display()),
+    )
+}
+
+/// Single line of `/proc/pid/maps`.
+#[derive(Debug, Clone)]
+pub(crate) struct MapsEntry {
+    pub start: usize,
+    pub end: usize,
+    pub read: bool,
+    pub write: bool,
+    pub execute: bool,
+    /// `s` instead of `p`, changes are visible to other processes mapping the same object.
+    pub shared: bool,
+    /// Offset inside of the mapped file.
+    pub offset: u64,
+    pub inode: u64,
+    /// Resident and dirty sizes in bytes, only parsed from `/proc/pid/smaps`.
+    pub rss: Option<usize>,
+    pub dirty: Option<usize>,
+    /// Path of the mapped file, pseudo-path like `[heap]` or empty string for anonymous mappings.
+    pub path: String,
+}
+
+impl MapsEntry {
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(6, ' ');
+        let (start, end) = fields.next()?.split_once('-')?;
+        let perms = fields.next()?.as_bytes();
+        let offset = u64::from_str_radix(fields.next()?, 16).ok()?;
+        let _device = fields.next()?;
+        let inode = fields.next()?.parse().ok()?;
+
+        Some(Self {
+            start: usize::from_str_radix(start, 16).ok()?,
+            end: usize::from_str_radix(end, 16).ok()?,
+            read: *perms.first()? == b'r',
+            write: *perms.get(1)? == b'w',
+            execute: *perms.get(2)? == b'x',
+            shared: *perms.get(3)? == b's',
+            offset,
+            inode,
+            rss: None,
+            dirty: None,
+            path: fields.next().unwrap_or_default().trim_start().to_string(),
+        })
+    }
+
+    /// Returns `true` if a file is mapped, as opposed to anonymous memory or pseudo-paths.
+    pub fn is_file(&self) -> bool {
+        self.path.starts_with('/')
+    }
+}
+
+/// Parses memory mappings of the process, ordered by address.
+pub(crate) fn maps(pid: u32) -> io::Result<Vec<MapsEntry>> {
+    let path = proc_dir(pid).join("maps");
+    fs::read_to_string(&path)?
+        .lines()
+        .map(|line| MapsEntry::parse(line).ok_or_else(|| invalid(&path)))
+        .collect()
+}
+
+/// Parses memory mappings of the process with their sizes from `/proc/pid/smaps`, ordered by address.
+/// Much slower to read than `/proc/pid/maps`, the kernel walks page tables of every mapping.
+fn smaps(pid: u32) -> io::Result<Vec<MapsEntry>> {
+    let path = proc_dir(pid).join("smaps");
+    let mut entries: Vec<MapsEntry> = vec![];
+    for line in fs::read_to_string(&path)?.lines() {
+        // Every mapping starts with a line in `maps` format followed by `Key: value` lines.
+        let (key, value) = match line.split_once(':') {
+            Some((key, value)) if !key.contains(' ') => (key, value),
+            _ => {
+                entries.push(MapsEntry::parse(line).ok_or_else(|| invalid(&path))?);
+                continue;
+            }
+        };
+        let entry = entries.last_mut().ok_or_else(|| invalid(&path))?;
+        let kb = || Some(value.trim().strip_suffix(" kB")?.parse::<usize>().ok()? * 1024);
+        match key {
+            "Rss" => entry.rss = kb(),
+            "Shared_Dirty" | "Private_Dirty" => {
+                entry.dirty = Some(entry.dirty.unwrap_or(0) + kb().unwrap_or(0))
+            }
+            _ => {}
+        }
+    }
+    Ok(entries)
+}
+
+/// Lists memory regions of the process from `/proc/pid/maps`.
+pub(crate) fn regions(pid: u32) -> io::Result<Vec<MemoryRegion>> {
+    Ok(into_regions(maps(pid)?))
+}
+
+/// Lists memory regions of the process with their sizes from `/proc/pid/smaps`.
+pub(crate) fn regions_with_usage(pid: u32) -> io::Result<Vec<MemoryRegion>> {
+    Ok(into_regions(smaps(pid)?))
+}
+
+/// Returns the region of the process that contains `address`.
+/// Addresses between mappings are returned as [`RegionKind::Free`] regions.
+pub(crate) fn query(pid: u32, address: usize) -> crate::Result<MemoryRegion> {
+    let regions = regions(pid)?;
+    let next = regions.iter().position(|r| r.end > address);
+    match next.map(|i| (i, &regions[i])) {
+        Some((_, region)) if region.start <= address => Ok(region.clone()),
+        Some((i, region)) => {
+            let start = i.checked_sub(1).map(|i| regions[i].end).unwrap_or(0);
+            Ok(free(start..region.start))
+        }
+        None => Err(FaitheError::QueryFailed),
+    }
+}
+
+/// Converts mappings into regions, files are images if any of their mappings is executable.
+fn into_regions(maps: Vec<MapsEntry>) -> Vec<MemoryRegion> {
+    // Base address of every file is its first mapping.
+    let mut files: HashMap<&str, (usize, bool)> = HashMap::new();
+    for map in maps.iter().filter(|m| m.is_file()) {
+        files.entry(&map.path).or_insert((map.start, false)).1 |= map.execute;
+    }
+
+    maps.iter()
+        .map(|map| {
+            let mut protection = MemoryProtection::NONE;
+            for (set, flag) in [
+                (map.read, MemoryProtection::READ),
+                (map.write, MemoryProtection::WRITE),
+                (map.execute, MemoryProtection::EXECUTE),
+            ] {
+                protection.set(flag, set);
+            }
+
+            let kind = match map.path.as_str() {
+                path if matches!(files.get(path), Some((_, true))) => RegionKind::Image,
+                path if files.contains_key(path) => RegionKind::Mapped,
+                "[heap]" => RegionKind::Heap,
+                path if path.starts_with("[stack") => RegionKind::Stack,
+                "[vdso]" | "[vvar]" | "[vvar_vclock]" | "[vsyscall]" => RegionKind::Vdso,
+                _ => RegionKind::Anonymous,
+            };
+
+            MemoryRegion {
+                start: map.start,
+                end: map.end,
+                size: map.end - map.start,
+                allocation_base: files.get(map.path.as_str()).map_or(map.start, |f| f.0),
+                protection,
+                shared: map.shared,
+                offset: map.offset,
+                inode: map.inode,
+                path: map.is_file().then(|| map.path.clone()),
+                kind,
+                rss: map.rss,
+                dirty: map.dirty,
+            }
+        })
+        .collect()
+}
+
+/// Unmapped memory between two mappings.
+fn free(range: Range<usize>) -> MemoryRegion {
+    MemoryRegion {
+        start: range.start,
+        end: range.end,
+        size: range.end - range.start,
+        allocation_base: range.start,
+        protection: MemoryProtection::NONE,
+        shared: false,
+        offset: 0,
+        inode: 0,
+        path: None,
+        kind: RegionKind::Free,
+        rss: None,
+        dirty: None,
+    }
+}
+
+/// Fields of `/proc/pid/stat` or `/proc/pid/task/tid/stat`.
+#[cfg(feature = "external")]
+#[derive(Debug, Clone)]
+pub(crate) struct Stat {
+    pub comm: String,
+    pub parent_id: u32,
+    pub priority: i32,
+    pub num_threads: u32,
+}
+
+/// Parses `stat` file of a process or a thread.
+#[cfg(feature = "external")]
+pub(crate) fn stat(path: &Path) -> io::Result<Stat> {
+    let content = fs::read_to_string(path)?;
+    let parse = || {
+        // `comm` may contain spaces and parentheses itself.
+        let (head, tail) = content.rsplit_once(')')?;
+        let (_, comm) = head.split_once('(')?;
+        // Fields after `comm`, starting from the third one.
+        let fields = tail.split_whitespace().collect::<Vec<_>>();
+
+        Some(Stat {
+            comm: comm.to_string(),
+            parent_id: fields.get(1)?.parse().ok()?,
+            priority: fields.get(15)?.parse().ok()?,
+            num_threads: fields.get(17)?.parse().ok()?,
+        })
+    };
+    parse().ok_or_else(|| invalid(path))
+}
+
+/// Returns ids of processes or threads listed in the directory, `/proc` or `/proc/pid/task`.
+#[cfg(feature = "external")]
+pub(crate) fn ids(dir: &Path) -> io::Result<Vec<u32>> {
+    Ok(fs::read_dir(dir)?
+        .filter_map(|e| e.ok()?.file_name().to_str()?.parse().ok())
+        .collect())
+}
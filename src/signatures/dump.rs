@@ -0,0 +1,361 @@
+use super::{SignatureDb, ValueType};
+use std::{collections::HashMap, fmt::Write, time::SystemTime};
+
+/// Language of the dumped offsets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DumpFormat {
+    /// Rust module with a nested module of `const`s for every dumped module.
+    Rust,
+    /// C++ header with a nested namespace of `constexpr`s for every dumped module.
+    Cpp,
+    /// C# static class with a nested static class of constants for every dumped module.
+    CSharp,
+    /// JSON object with an object of offsets for every dumped module.
+    Json,
+}
+
+/// Returns 64-bit FNV-1a hash of the data, used to detect that a module has changed since the dump.
+/// ```
+/// # use faithe::signatures::hash_image;
+/// assert_eq!(hash_image(b""), 0xCBF29CE484222325);
+/// assert_ne!(hash_image(b"game.exe v1"), hash_image(b"game.exe v2"));
+/// ```
+pub fn hash_image(data: &[u8]) -> u64 {
+    data.iter().fold(0xCBF29CE484222325, |hash, b| {
+        (hash ^ *b as u64).wrapping_mul(0x100000001B3)
+    })
+}
+
+/// Single dumped offset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DumpEntry {
+    /// Name of the offset.
+    pub name: String,
+    /// Offset from the base of the module.
+    pub rva: usize,
+    /// Type of the value at the offset.
+    pub ty: Option<ValueType>,
+}
+
+/// Offsets inside of a single module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleDump {
+    /// Name of the module, e.g. `client.dll`.
+    pub name: String,
+    /// Base address of the module the offsets were resolved at.
+    pub base: usize,
+    /// Size of the module's image.
+    pub size: usize,
+    /// Hash of the module, see [`hash_image`].
+    pub hash: u64,
+    /// Dumped offsets.
+    pub entries: Vec<DumpEntry>,
+}
+
+impl ModuleDump {
+    /// Creates an empty dump of the module loaded at `base`,
+    /// e.g. from `ModuleEntry::base_address` and `ModuleEntry::size`.
+    pub fn new(name: impl Into<String>, base: usize, size: usize, hash: u64) -> Self {
+        Self {
+            name: name.into(),
+            base,
+            size,
+            hash,
+            entries: vec![],
+        }
+    }
+
+    /// Adds an offset from the base of the module.
+    pub fn add(&mut self, name: impl Into<String>, rva: usize, ty: Option<ValueType>) -> &mut Self {
+        self.entries.push(DumpEntry {
+            name: name.into(),
+            rva,
+            ty,
+        });
+        self
+    }
+
+    /// Adds every signature of the database that belongs to the module and was resolved.
+    /// Resolved addresses are converted into offsets from the base of the module.
+    pub fn add_resolved(
+        &mut self,
+        db: &SignatureDb,
+        resolved: &HashMap<String, usize>,
+    ) -> &mut Self {
+        let entries = db
+            .signatures
+            .iter()
+            .filter(|s| s.module == self.name)
+            .filter_map(|s| {
+                resolved.get(&s.name).map(|address| DumpEntry {
+                    name: s.name.clone(),
+                    rva: address.wrapping_sub(self.base),
+                    ty: s.ty,
+                })
+            })
+            .collect::<Vec<_>>();
+        self.entries.extend(entries);
+        self
+    }
+}
+
+/// Resolved offsets of several modules that can be rendered as Rust, C++, C# or JSON source.
+/// ```
+/// # use faithe::signatures::{Dump, DumpFormat, ModuleDump, ValueType};
+/// let mut client = ModuleDump::new("client.dll", 0x7FF600000000, 0x200000, 0xAABB);
+/// client
+///     .add("LocalPlayer", 0x1234, Some(ValueType::Ptr))
+///     .add("ViewMatrix", 0x5678, None);
+///
+/// let mut dump = Dump::new();
+/// dump.timestamp = 1700000000;
+/// dump.modules.push(client);
+///
+/// let rust = dump.render(DumpFormat::Rust);
+/// assert!(rust.contains("pub mod client_dll {"));
+/// assert!(rust.contains("pub const LocalPlayer: usize = 0x1234;"));
+/// assert!(rust.contains("pub const TIMESTAMP: u64 = 1700000000;"));
+///
+/// let cpp = dump.render(DumpFormat::Cpp);
+/// assert!(cpp.contains("constexpr std::uintptr_t ViewMatrix = 0x5678;"));
+/// assert!(cpp.contains("constexpr std::size_t module_size = 0x200000;"));
+///
+/// let cs = dump.render(DumpFormat::CSharp);
+/// assert!(cs.contains("public static class client_dll"));
+/// assert!(cs.contains("public const ulong ModuleHash = 0xAABB;"));
+///
+/// let json = dump.render(DumpFormat::Json);
+/// assert!(json.contains(r#""LocalPlayer": 4660"#));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dump {
+    /// Time the offsets were dumped at, in seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Dumped modules.
+    pub modules: Vec<ModuleDump>,
+}
+
+impl Default for Dump {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Turns the name into a valid identifier of all supported languages.
+fn ident(name: &str) -> String {
+    let mut ident = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    if !ident.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        ident.insert(0, '_');
+    }
+    ident
+}
+
+fn json_str(s: &str) -> String {
+    let mut out = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn type_name(ty: Option<ValueType>) -> &'static str {
+    match ty {
+        Some(ValueType::U8) => "u8",
+        Some(ValueType::U16) => "u16",
+        Some(ValueType::U32) => "u32",
+        Some(ValueType::U64) => "u64",
+        Some(ValueType::I8) => "i8",
+        Some(ValueType::I16) => "i16",
+        Some(ValueType::I32) => "i32",
+        Some(ValueType::I64) => "i64",
+        Some(ValueType::F32) => "f32",
+        Some(ValueType::F64) => "f64",
+        Some(ValueType::Bool) => "bool",
+        Some(ValueType::Ptr) => "ptr",
+        Some(ValueType::Fn) => "fn",
+        None => "",
+    }
+}
+
+impl Dump {
+    /// Creates an empty dump stamped with the current time.
+    pub fn new() -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            modules: vec![],
+        }
+    }
+
+    /// Renders the offsets as source code in the format.
+    pub fn render(&self, format: DumpFormat) -> String {
+        // Writing into a `String` never fails.
+        let mut out = String::new();
+        match format {
+            DumpFormat::Rust => self.render_rust(&mut out),
+            DumpFormat::Cpp => self.render_cpp(&mut out),
+            DumpFormat::CSharp => self.render_csharp(&mut out),
+            DumpFormat::Json => self.render_json(&mut out),
+        }
+        .unwrap();
+        out
+    }
+
+    fn render_rust(&self, out: &mut String) -> std::fmt::Result {
+        writeln!(out, "// Generated by faithe, do not edit.")?;
+        writeln!(out, "#[allow(non_upper_case_globals, dead_code)]")?;
+        writeln!(out, "pub mod offsets {{")?;
+        writeln!(out, "    /// Unix time the offsets were dumped at.")?;
+        writeln!(out, "    pub const TIMESTAMP: u64 = {};", self.timestamp)?;
+        for module in &self.modules {
+            writeln!(out)?;
+            writeln!(out, "    /// `{}`", module.name)?;
+            writeln!(out, "    pub mod {} {{", ident(&module.name))?;
+            writeln!(
+                out,
+                "        pub const MODULE_SIZE: usize = {:#X};",
+                module.size
+            )?;
+            writeln!(
+                out,
+                "        pub const MODULE_HASH: u64 = {:#X};",
+                module.hash
+            )?;
+            for entry in &module.entries {
+                if entry.ty.is_some() {
+                    writeln!(out, "        /// `{}`", type_name(entry.ty))?;
+                }
+                writeln!(
+                    out,
+                    "        pub const {}: usize = {:#X};",
+                    ident(&entry.name),
+                    entry.rva
+                )?;
+            }
+            writeln!(out, "    }}")?;
+        }
+        writeln!(out, "}}")
+    }
+
+    fn render_cpp(&self, out: &mut String) -> std::fmt::Result {
+        writeln!(out, "// Generated by faithe, do not edit.")?;
+        writeln!(out, "#pragma once")?;
+        writeln!(out, "#include <cstddef>")?;
+        writeln!(out, "#include <cstdint>")?;
+        writeln!(out)?;
+        writeln!(out, "namespace offsets {{")?;
+        writeln!(out, "// Unix time the offsets were dumped at.")?;
+        writeln!(
+            out,
+            "constexpr std::uint64_t timestamp = {};",
+            self.timestamp
+        )?;
+        for module in &self.modules {
+            writeln!(out)?;
+            writeln!(out, "// {}", module.name)?;
+            writeln!(out, "namespace {} {{", ident(&module.name))?;
+            writeln!(
+                out,
+                "constexpr std::size_t module_size = {:#X};",
+                module.size
+            )?;
+            writeln!(
+                out,
+                "constexpr std::uint64_t module_hash = {:#X};",
+                module.hash
+            )?;
+            for entry in &module.entries {
+                write!(
+                    out,
+                    "constexpr std::uintptr_t {} = {:#X};",
+                    ident(&entry.name),
+                    entry.rva
+                )?;
+                match entry.ty {
+                    Some(_) => writeln!(out, " // {}", type_name(entry.ty))?,
+                    None => writeln!(out)?,
+                }
+            }
+            writeln!(out, "}} // namespace {}", ident(&module.name))?;
+        }
+        writeln!(out, "}} // namespace offsets")
+    }
+
+    fn render_csharp(&self, out: &mut String) -> std::fmt::Result {
+        writeln!(out, "// Generated by faithe, do not edit.")?;
+        writeln!(out, "public static class Offsets")?;
+        writeln!(out, "{{")?;
+        writeln!(out, "    // Unix time the offsets were dumped at.")?;
+        writeln!(
+            out,
+            "    public const ulong Timestamp = {};",
+            self.timestamp
+        )?;
+        for module in &self.modules {
+            writeln!(out)?;
+            writeln!(out, "    // {}", module.name)?;
+            writeln!(out, "    public static class {}", ident(&module.name))?;
+            writeln!(out, "    {{")?;
+            writeln!(
+                out,
+                "        public const ulong ModuleSize = {:#X};",
+                module.size
+            )?;
+            writeln!(
+                out,
+                "        public const ulong ModuleHash = {:#X};",
+                module.hash
+            )?;
+            for entry in &module.entries {
+                write!(
+                    out,
+                    "        public const ulong {} = {:#X};",
+                    ident(&entry.name),
+                    entry.rva
+                )?;
+                match entry.ty {
+                    Some(_) => writeln!(out, " // {}", type_name(entry.ty))?,
+                    None => writeln!(out)?,
+                }
+            }
+            writeln!(out, "    }}")?;
+        }
+        writeln!(out, "}}")
+    }
+
+    fn render_json(&self, out: &mut String) -> std::fmt::Result {
+        writeln!(out, "{{")?;
+        write!(out, "  \"timestamp\": {}", self.timestamp)?;
+        for module in &self.modules {
+            writeln!(out, ",")?;
+            writeln!(out, "  {}: {{", json_str(&module.name))?;
+            writeln!(out, "    \"module_size\": {},", module.size)?;
+            write!(
+                out,
+                "    \"module_hash\": {}",
+                json_str(&format!("{:#X}", module.hash))
+            )?;
+            for entry in &module.entries {
+                writeln!(out, ",")?;
+                write!(out, "    {}: {}", json_str(&entry.name), entry.rva)?;
+            }
+            writeln!(out)?;
+            write!(out, "  }}")?;
+        }
+        writeln!(out)?;
+        writeln!(out, "}}")
+    }
+}
@@ -0,0 +1,344 @@
+use crate::{
+    pattern::{Captured, Pattern, PatternMatch},
+    FaitheError,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+mod dump;
+pub use dump::*;
+mod target;
+pub use target::*;
+
+/// The latest version of the database format.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// How the address of the signature is located, the same as modes of offsets declared with [`crate::global!`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    /// Base of the module plus `offset`, plus `add`.
+    Explicit,
+    /// Address of the pattern occurence plus `add`.
+    Pattern,
+    /// Address referred to by the instruction at the pattern occurence plus `add`,
+    /// see [`crate::pattern::instruction_target`].
+    Smart,
+}
+
+/// Action applied to the located address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Step {
+    /// Replaces the address with the value captured by the pattern at the index.
+    Capture(usize),
+    /// Replaces the address with the one referred to by rel32 displacement located at the offset from it.
+    Rel32(usize),
+    /// Reads a pointer at the address.
+    Deref,
+    /// Adds the value to the address.
+    Add(isize),
+}
+
+/// Type of the value located at the signature's address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[allow(missing_docs)]
+pub enum ValueType {
+    U8,
+    U16,
+    U32,
+    U64,
+    I8,
+    I16,
+    I32,
+    I64,
+    F32,
+    F64,
+    Bool,
+    /// Pointer to data.
+    Ptr,
+    /// Function.
+    Fn,
+}
+
+/// Single entry of the database.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Signature {
+    /// Name the resolved address is reported under.
+    pub name: String,
+    /// Name of the module the signature is searched in.
+    pub module: String,
+    /// Resolution mode, if omitted it is `pattern` when pattern is present and `explicit` otherwise.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<Mode>,
+    /// Offset from the base of the module for `explicit` mode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
+    /// Pattern for `pattern` and `smart` modes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<Pattern>,
+    /// Value added to the located address before the steps are applied.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub add: isize,
+    /// Steps applied to the located address in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub steps: Vec<Step>,
+    /// Type of the value at the resolved address.
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub ty: Option<ValueType>,
+}
+
+fn is_zero(v: &isize) -> bool {
+    *v == 0
+}
+
+impl Signature {
+    /// Returns the resolution mode of the signature.
+    pub fn mode(&self) -> Mode {
+        self.mode.unwrap_or(if self.pattern.is_some() {
+            Mode::Pattern
+        } else {
+            Mode::Explicit
+        })
+    }
+
+    /// Resolves the address of the signature in the target.
+    /// ```
+    /// # use faithe::{memory::MemoryReader, pattern::{Pattern, PatternMatch}, signatures::*};
+    /// /// Dump of the module loaded at `0x1000`.
+    /// struct Dump(Vec<u8>);
+    ///
+    /// impl MemoryReader for Dump {
+    ///     fn read_bytes(&self, address: usize, buf: &mut [u8]) -> Result<usize, faithe::FaitheError> {
+    ///         let data = &self.0[address - 0x1000..];
+    ///         let len = data.len().min(buf.len());
+    ///         buf[..len].copy_from_slice(&data[..len]);
+    ///         Ok(len)
+    ///     }
+    /// }
+    ///
+    /// impl SignatureTarget for Dump {
+    ///     fn module_base(&self, _: &str) -> Result<usize, faithe::FaitheError> {
+    ///         Ok(0x1000)
+    ///     }
+    ///
+    ///     fn find_match(&self, _: &str, pat: &Pattern) -> Result<Option<PatternMatch>, faithe::FaitheError> {
+    ///         Ok(pat.find_matches(&self.0, 0x1000).next())
+    ///     }
+    ///
+    ///     fn pointer_size(&self) -> usize {
+    ///         8
+    ///     }
+    /// }
+    ///
+    /// let mut dump = vec![0x90; 0x30];
+    /// // mov rax, [rip + 0x10]
+    /// dump[0x10..0x17].copy_from_slice(&[0x48, 0x8B, 0x05, 0x10, 0, 0, 0]);
+    /// dump[0x27..0x2F].copy_from_slice(&0xDEAD0000u64.to_le_bytes());
+    /// let dump = Dump(dump);
+    ///
+    /// let sig = |mode, pattern: Option<&str>, steps| Signature {
+    ///     name: "LocalPlayer".into(),
+    ///     module: "game.exe".into(),
+    ///     mode,
+    ///     offset: Some(0x20),
+    ///     pattern: pattern.map(|p| p.parse().unwrap()),
+    ///     add: 0,
+    ///     steps,
+    ///     ty: None,
+    /// };
+    /// assert_eq!(sig(None, None, vec![]).resolve(&dump)?, 0x1020);
+    /// assert_eq!(sig(None, Some("48 8B 05"), vec![]).resolve(&dump)?, 0x1010);
+    /// assert_eq!(sig(Some(Mode::Smart), Some("48 8B 05"), vec![]).resolve(&dump)?, 0x1027);
+    /// assert_eq!(sig(None, Some("48 8B 05 $rel32"), vec![Step::Capture(0)]).resolve(&dump)?, 0x1027);
+    /// assert_eq!(
+    ///     sig(None, Some("48 8B 05"), vec![Step::Rel32(3), Step::Deref, Step::Add(-0x10)]).resolve(&dump)?,
+    ///     0xDEACFFF0
+    /// );
+    /// assert!(sig(None, Some("CC CC"), vec![]).resolve(&dump).is_err());
+    ///
+    /// // Offsets from a database that don't fit into the address space are rejected.
+    /// let sig = Signature { offset: Some(usize::MAX), ..sig(None, None, vec![]) };
+    /// assert!(matches!(sig.resolve(&dump), Err(faithe::FaitheError::InvalidSignature)));
+    /// # Ok::<(), faithe::FaitheError>(())
+    /// ```
+    pub fn resolve(&self, target: &impl SignatureTarget) -> crate::Result<usize> {
+        let mode = self.mode();
+        let found = match (mode, &self.pattern) {
+            (Mode::Explicit, _) => None,
+            (_, Some(pat)) => Some(
+                target
+                    .find_match(&self.module, pat)?
+                    .ok_or(FaitheError::PatternNotFound)?,
+            ),
+            (_, None) => return Err(FaitheError::InvalidSignature),
+        };
+
+        let mut address = match &found {
+            None => {
+                let offset = self.offset.ok_or(FaitheError::InvalidSignature)?;
+                target
+                    .module_base(&self.module)?
+                    .checked_add(offset)
+                    .ok_or(FaitheError::InvalidSignature)?
+                    .wrapping_add(self.add as usize)
+            }
+            Some(found) if mode == Mode::Smart => {
+                let at = found.address.wrapping_add(self.add as usize);
+                let mut code = [0; 15];
+                let read = target.read_bytes(at, &mut code)?;
+                let bitness = target.pointer_size() as u32 * 8;
+                crate::pattern::instruction_target(&code[..read], at, bitness)?
+            }
+            Some(found) => found.address.wrapping_add(self.add as usize),
+        };
+
+        for step in &self.steps {
+            address = apply(*step, address, found.as_ref(), target)?;
+        }
+        Ok(address)
+    }
+}
+
+fn apply(
+    step: Step,
+    address: usize,
+    found: Option<&PatternMatch>,
+    target: &impl SignatureTarget,
+) -> crate::Result<usize> {
+    Ok(match step {
+        Step::Capture(idx) => match found.and_then(|m| m.get(idx)) {
+            Some(Captured::Address(address)) => *address,
+            Some(captured) => captured.as_u64() as usize,
+            None => return Err(FaitheError::InvalidSignature),
+        },
+        Step::Rel32(offset) => {
+            let mut disp = [0; 4];
+            let at = address.wrapping_add(offset);
+            target.read_exact(at, &mut disp)?;
+            at.wrapping_add(4)
+                .wrapping_add(i32::from_le_bytes(disp) as usize)
+        }
+        Step::Deref => {
+            let mut ptr = [0; 8];
+            let size = target.pointer_size();
+            target.read_exact(address, &mut ptr[..size])?;
+            u64::from_le_bytes(ptr) as usize
+        }
+        Step::Add(value) => address.wrapping_add(value as usize),
+    })
+}
+
+/// Declarative list of signatures, usually loaded from a TOML or JSON file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignatureDb {
+    /// Version of the format the database is written in.
+    #[serde(default = "format_version")]
+    pub version: u32,
+    /// All signatures of the database.
+    #[serde(default, rename = "signature")]
+    pub signatures: Vec<Signature>,
+}
+
+fn format_version() -> u32 {
+    FORMAT_VERSION
+}
+
+impl Default for SignatureDb {
+    fn default() -> Self {
+        Self {
+            version: FORMAT_VERSION,
+            signatures: vec![],
+        }
+    }
+}
+
+impl SignatureDb {
+    #[cfg(any(feature = "toml", feature = "serde_json"))]
+    fn checked(self) -> crate::Result<Self> {
+        if self.version > FORMAT_VERSION {
+            Err(FaitheError::InvalidDatabase(format!(
+                "Unsupported format version {}.",
+                self.version
+            )))
+        } else {
+            Ok(self)
+        }
+    }
+
+    /// Parses the database from TOML, where every signature is a `[[signature]]` table.
+    /// ```
+    /// # use faithe::signatures::{Mode, SignatureDb, ValueType};
+    /// let db = SignatureDb::from_toml(r#"
+    ///     version = 1
+    ///
+    ///     [[signature]]
+    ///     name = "ViewMatrix"
+    ///     module = "client.dll"
+    ///     mode = "smart"
+    ///     pattern = "48 8D 0D ? ? ? ? 48 C1 E0 06"
+    ///     add = 0
+    ///     type = "ptr"
+    /// "#).unwrap();
+    /// assert_eq!(db.signatures[0].mode(), Mode::Smart);
+    /// assert_eq!(db.signatures[0].ty, Some(ValueType::Ptr));
+    /// ```
+    #[cfg(feature = "toml")]
+    pub fn from_toml(s: &str) -> crate::Result<Self> {
+        toml::from_str::<Self>(s)
+            .map_err(|e| FaitheError::InvalidDatabase(e.to_string()))?
+            .checked()
+    }
+
+    /// Serializes the database into TOML.
+    #[cfg(feature = "toml")]
+    pub fn to_toml(&self) -> crate::Result<String> {
+        toml::to_string(self).map_err(|e| FaitheError::InvalidDatabase(e.to_string()))
+    }
+
+    /// Parses the database from JSON, where signatures are listed in `signature` array.
+    /// ```
+    /// # use faithe::signatures::{Mode, SignatureDb, Step};
+    /// let db = SignatureDb::from_json(r#"{
+    ///     "version": 1,
+    ///     "signature": [
+    ///         { "name": "Health", "module": "game.exe", "offset": 4096, "type": "f32" },
+    ///         {
+    ///             "name": "LocalPlayer",
+    ///             "module": "game.exe",
+    ///             "pattern": "48 8B 05 ? ? ? ? 48 85 C0",
+    ///             "steps": [{ "rel32": 3 }, "deref", { "add": 16 }]
+    ///         }
+    ///     ]
+    /// }"#).unwrap();
+    /// assert_eq!(db.signatures[0].mode(), Mode::Explicit);
+    /// assert_eq!(db.signatures[1].steps, [Step::Rel32(3), Step::Deref, Step::Add(16)]);
+    /// ```
+    #[cfg(feature = "serde_json")]
+    pub fn from_json(s: &str) -> crate::Result<Self> {
+        serde_json::from_str::<Self>(s)
+            .map_err(|e| FaitheError::InvalidDatabase(e.to_string()))?
+            .checked()
+    }
+
+    /// Serializes the database into JSON.
+    #[cfg(feature = "serde_json")]
+    pub fn to_json(&self) -> crate::Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| FaitheError::InvalidDatabase(e.to_string()))
+    }
+
+    /// Returns the signature with the name.
+    pub fn get(&self, name: impl AsRef<str>) -> Option<&Signature> {
+        self.signatures.iter().find(|s| s.name == name.as_ref())
+    }
+
+    /// Resolves every signature in the target, stops at the first one that fails.
+    /// Use [`Signature::resolve`] to handle failures one by one.
+    pub fn resolve(&self, target: &impl SignatureTarget) -> crate::Result<HashMap<String, usize>> {
+        self.signatures
+            .iter()
+            .map(|s| Ok((s.name.clone(), s.resolve(target)?)))
+            .collect()
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{
+    memory::MemoryReader,
+    pattern::{Pattern, PatternMatch},
+};
+
+/// Something signatures can be resolved in: the current process, another process or an image on disk.
+/// Memory of the target is read with [`MemoryReader`].
+pub trait SignatureTarget: MemoryReader {
+    /// Returns the base address of the module.
+    fn module_base(&self, module: &str) -> crate::Result<usize>;
+
+    /// Returns the first occurence of the pattern in the module with its captured values.
+    fn find_match(&self, module: &str, pat: &Pattern) -> crate::Result<Option<PatternMatch>>;
+
+    /// Size of pointers read by [`super::Step::Deref`].
+    fn pointer_size(&self) -> usize {
+        core::mem::size_of::<usize>()
+    }
+}
+
+#[cfg(any(windows, target_os = "linux"))]
+impl SignatureTarget for crate::memory::CurrentProcess {
+    fn module_base(&self, module: &str) -> crate::Result<usize> {
+        crate::internal::get_module_address(module).map(|base| base as usize)
+    }
+
+    fn find_match(&self, module: &str, pat: &Pattern) -> crate::Result<Option<PatternMatch>> {
+        crate::internal::find_pattern_match(module, pat)
+    }
+}
+
+#[cfg(all(any(windows, target_os = "linux"), feature = "external"))]
+impl SignatureTarget for crate::process::OwnedProcess {
+    fn module_base(&self, module: &str) -> crate::Result<usize> {
+        self.modules()?
+            .find(|m| m.name == module)
+            .map(|m| m.base_address)
+            .ok_or(crate::FaitheError::ModuleNotFound)
+    }
+
+    fn find_match(&self, module: &str, pat: &Pattern) -> crate::Result<Option<PatternMatch>> {
+        self.find_pattern_match(module, pat)
+    }
+}
+
+/// Image on disk, module names are ignored and the image is searched for every signature.
+/// Addresses are virtual addresses at the image's preferred base.
+#[cfg(feature = "memmap2")]
+impl SignatureTarget for crate::pattern::FileImage {
+    fn module_base(&self, _module: &str) -> crate::Result<usize> {
+        Ok(self.image_base())
+    }
+
+    fn find_match(&self, _module: &str, pat: &Pattern) -> crate::Result<Option<PatternMatch>> {
+        let found = match self.sections() {
+            [] => pat.find_matches(self.data(), 0).next(),
+            sections => sections
+                .iter()
+                .filter_map(|s| self.section(&s.name))
+                .find_map(|s| pat.find_matches(s.data, s.section.address).next()),
+        };
+        Ok(found)
+    }
+
+    fn pointer_size(&self) -> usize {
+        crate::pattern::FileImage::pointer_size(self)
+    }
+}
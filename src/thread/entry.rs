@@ -0,0 +1,22 @@
+/// Represents single running thread in a process.
+#[derive(Debug, Clone)]
+pub struct ThreadEntry {
+    /// Id of the process this thread is running in.
+    pub process_id: u32,
+    /// Id of the thread.
+    pub thread_id: u32,
+    /// Priority of the thread.
+    pub base_priority: i32,
+}
+
+#[cfg(windows)]
+impl ThreadEntry {
+    /// Opens thread
+    pub fn open(
+        &self,
+        inherit_handle: bool,
+        desired_access: windows::Win32::System::Threading::THREAD_ACCESS_RIGHTS,
+    ) -> crate::Result<super::OwnedThread> {
+        super::OwnedThread::open(self.thread_id, inherit_handle, desired_access)
+    }
+}
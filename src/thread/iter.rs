@@ -1,36 +1,11 @@
 use windows::Win32::{
     Foundation::HANDLE,
-    System::{
-        Diagnostics::ToolHelp::{
-            CreateToolhelp32Snapshot, Thread32First, Thread32Next, TH32CS_SNAPTHREAD, THREADENTRY32,
-        },
-        Threading::THREAD_ACCESS_RIGHTS,
+    System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Thread32First, Thread32Next, TH32CS_SNAPTHREAD, THREADENTRY32,
     },
 };
 use crate::{size_of, FaitheError};
-use super::OwnedThread;
-
-/// Represents single running thread in a process.
-#[derive(Debug, Clone)]
-pub struct ThreadEntry {
-    /// Id of the process this thread is running in.
-    pub process_id: u32,
-    /// Id of the thread.
-    pub thread_id: u32,
-    /// Priority of the thread.
-    pub base_priority: i32,
-}
-
-impl ThreadEntry {
-    /// Opens thread
-    pub fn open(
-        &self,
-        inherit_handle: bool,
-        desired_access: THREAD_ACCESS_RIGHTS,
-    ) -> crate::Result<OwnedThread> {
-        OwnedThread::open(self.thread_id, inherit_handle, desired_access)
-    }
-}
+use super::ThreadEntry;
 
 impl From<THREADENTRY32> for ThreadEntry {
     fn from(te: THREADENTRY32) -> Self {
@@ -0,0 +1,50 @@
+use super::ThreadEntry;
+use crate::procfs;
+
+/// Iterator over running threads in the process.
+/// Threads are listed from `/proc/pid/task` when the iterator is created,
+/// threads that exited by the time they are reached are skipped.
+/// ```
+/// # use faithe::thread::ThreadIterator;
+/// let (tx, rx) = std::sync::mpsc::channel::<()>();
+/// let worker = std::thread::spawn(move || rx.recv());
+///
+/// let threads = ThreadIterator::new(std::process::id())?.collect::<Vec<_>>();
+/// assert!(threads.len() >= 2);
+/// assert!(threads.iter().any(|t| t.thread_id == std::process::id()));
+///
+/// drop(tx);
+/// worker.join().unwrap().ok();
+/// # Ok::<(), faithe::FaitheError>(())
+/// ```
+pub struct ThreadIterator {
+    process_id: u32,
+    tids: std::vec::IntoIter<u32>,
+}
+
+impl ThreadIterator {
+    /// Creates new iterator over threads in process with id `process_id`.
+    pub fn new(process_id: u32) -> crate::Result<Self> {
+        let tids = procfs::ids(&procfs::proc_dir(process_id).join("task"))?;
+        Ok(Self {
+            process_id,
+            tids: tids.into_iter(),
+        })
+    }
+}
+
+impl Iterator for ThreadIterator {
+    type Item = ThreadEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let task = procfs::proc_dir(self.process_id).join("task");
+        self.tids.find_map(|tid| {
+            let stat = procfs::stat(&task.join(tid.to_string()).join("stat")).ok()?;
+            Some(ThreadEntry {
+                process_id: self.process_id,
+                thread_id: tid,
+                base_priority: stat.priority,
+            })
+        })
+    }
+}
@@ -0,0 +1,90 @@
+/// Defines a set of flags with the same name and values as the Windows type, so the same code compiles on both platforms.
+macro_rules! win_flags {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[allow(non_camel_case_types)]
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+        pub struct $name(pub u32);
+
+        impl $name {
+            /// Returns `true` if all of the flags in `other` are present.
+            #[inline]
+            pub const fn contains(self, other: Self) -> bool {
+                self.0 & other.0 == other.0
+            }
+        }
+
+        impl core::ops::BitOr for $name {
+            type Output = Self;
+
+            #[inline]
+            fn bitor(self, rhs: Self) -> Self {
+                Self(self.0 | rhs.0)
+            }
+        }
+
+        impl core::ops::BitOrAssign for $name {
+            #[inline]
+            fn bitor_assign(&mut self, rhs: Self) {
+                self.0 |= rhs.0;
+            }
+        }
+    };
+}
+
+/// Flags that can be used with [`crate::process::OwnedProcess::open_by_id`], [`crate::process::OwnedProcess::open_by_name`] and etc.
+/// Values are the same as on Windows, so the same code opens processes on both platforms.
+/// Linux has no per-handle access rights, `PROCESS_VM_WRITE` only makes `/proc/pid/mem` be opened for writing.
+#[cfg(feature = "external")]
+pub mod access_rights {
+    win_flags! {
+        /// Access rights to a process.
+        PROCESS_ACCESS_RIGHTS
+    }
+
+    /// Terminating the process.
+    pub const PROCESS_TERMINATE: PROCESS_ACCESS_RIGHTS = PROCESS_ACCESS_RIGHTS(0x1);
+    /// Creating threads in the process.
+    pub const PROCESS_CREATE_THREAD: PROCESS_ACCESS_RIGHTS = PROCESS_ACCESS_RIGHTS(0x2);
+    /// Changing the process's address space.
+    pub const PROCESS_VM_OPERATION: PROCESS_ACCESS_RIGHTS = PROCESS_ACCESS_RIGHTS(0x8);
+    /// Reading the process's memory.
+    pub const PROCESS_VM_READ: PROCESS_ACCESS_RIGHTS = PROCESS_ACCESS_RIGHTS(0x10);
+    /// Writing the process's memory.
+    pub const PROCESS_VM_WRITE: PROCESS_ACCESS_RIGHTS = PROCESS_ACCESS_RIGHTS(0x20);
+    /// Querying information about the process.
+    pub const PROCESS_QUERY_INFORMATION: PROCESS_ACCESS_RIGHTS = PROCESS_ACCESS_RIGHTS(0x400);
+    /// Suspending and resuming the process.
+    pub const PROCESS_SUSPEND_RESUME: PROCESS_ACCESS_RIGHTS = PROCESS_ACCESS_RIGHTS(0x800);
+    /// Querying limited information about the process.
+    pub const PROCESS_QUERY_LIMITED_INFORMATION: PROCESS_ACCESS_RIGHTS =
+        PROCESS_ACCESS_RIGHTS(0x1000);
+    /// All possible access rights.
+    pub const PROCESS_ALL_ACCESS: PROCESS_ACCESS_RIGHTS = PROCESS_ACCESS_RIGHTS(0x1FFFFF);
+}
+
+/// Types of allocation that can be used with [`crate::internal::allocate`]
+pub mod allocation_types {
+    win_flags! {
+        /// Type of memory allocation.
+        VIRTUAL_ALLOCATION_TYPE
+    }
+
+    /// Makes reserved pages accessible with the requested protection.
+    pub const MEM_COMMIT: VIRTUAL_ALLOCATION_TYPE = VIRTUAL_ALLOCATION_TYPE(0x1000);
+    /// Maps new pages that can't be accessed until they are committed.
+    pub const MEM_RESERVE: VIRTUAL_ALLOCATION_TYPE = VIRTUAL_ALLOCATION_TYPE(0x2000);
+}
+
+/// Types of freeing that can be used with [`crate::internal::free`]
+pub mod free_types {
+    win_flags! {
+        /// Type of memory freeing.
+        VIRTUAL_FREE_TYPE
+    }
+
+    /// Drops contents of the pages and makes them inaccessible, the address range stays reserved.
+    pub const MEM_DECOMMIT: VIRTUAL_FREE_TYPE = VIRTUAL_FREE_TYPE(0x4000);
+    /// Unmaps the pages.
+    pub const MEM_RELEASE: VIRTUAL_FREE_TYPE = VIRTUAL_FREE_TYPE(0x8000);
+}
@@ -1,10 +1,13 @@
 cfg_if::cfg_if! {
-    if #[cfg(not(feature = "no-std"))] {
+    if #[cfg(all(windows, not(feature = "no-std")))] {
         mod winapi;
         pub use winapi::*;
 
         mod memory;
         pub use memory::*;
+    } else if #[cfg(all(target_os = "linux", not(feature = "no-std")))] {
+        mod linux;
+        pub use linux::*;
     }
 }
 
@@ -97,6 +97,6 @@ impl MemoryProtection {
     /// For any other protection `None` is returned.
     #[cfg(unix)]
     pub fn from_os(prot: i32) -> Option<Self> {
-        Self::from_bits(prot)
+        Self::from_bits(prot as u32)
     }
 }